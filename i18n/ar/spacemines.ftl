@@ -0,0 +1,244 @@
+app-title = ألغام الفضاء
+about = حول
+new-game = جديدة
+seed = البذرة
+undo = تراجع
+hint = تلميح
+options = خيارات
+view = عرض
+zoom-in = تكبير
+zoom-out = تصغير
+fit-window = ملاءمة النافذة
+settings = الإعدادات
+gameplay = اللعب
+appearance = المظهر
+difficulty = الصعوبة
+beginner = مبتدئ
+intermediate = متوسط
+expert = خبير
+safe-start = بداية آمنة
+safe-start-tile = الخانة المنقورة
+safe-start-relocate = نقل اللغم بعيدًا
+safe-start-opening = فتحة مضمونة
+safe-start-no-guess = لا حاجة للتخمين
+question-marks = علامات الاستفهام
+confirm-prompts = طلبات التأكيد
+topology = شكل اللوحة
+topology-rectangle = مستطيل
+topology-cylinder = أسطوانة
+topology-torus = طارة
+topology-cylinder-edges = الحافتان اليسرى واليمنى متصلتان.
+topology-torus-edges = جميع الحواف متصلة.
+grid = شكل الخانة
+grid-square = مربع
+grid-hex = سداسي
+grid-hex-rows = الخانات سداسية، وكل صف ثانٍ مزاح إلى اليمين بمقدار نصف خانة.
+neighbourhood-von-neumann-rule = تعدّ الأرقام الخانات التي فوق وتحت ويسار ويمين الخانة فقط.
+neighbourhood-knight-rule = تعدّ الأرقام الخانات التي يمكن لحصان الشطرنج القفز إليها.
+neighbourhood-radius-2-rule = تعدّ الأرقام كل الخانات التي تبعد خطوتين على الأكثر.
+neighbourhood-custom-rule = تعدّ الأرقام { $count ->
+    [one] جارًا مخصصًا واحدًا
+   *[other] { $count } من الجيران المخصصين
+}.
+mines-per-tile-rule = يمكن لكل خانة أن تحوي حتى { $count } ألغام، والأرقام تعدّها كلها.
+rule-liar-legend = كل رقم يكذب، والعدد الحقيقي أكثر أو أقل بواحد بالضبط.
+rule-double-legend = كل لغم يُحسب مرتين.
+rule-negative-legend = بعض الألغام تُحسب سالب واحد، لذا قد تكون الأرقام سالبة.
+rule-orthogonal-legend = الأرقام تعدّ فقط الخانات في الأعلى والأسفل واليسار واليمين.
+layers = الطبقات
+neighbourhood = الجيران
+neighbourhood-moore = كل الخانات الملامسة
+neighbourhood-von-neumann = الخانات المتعامدة
+neighbourhood-knight = حركات الحصان
+neighbourhood-radius-2 = نصف قطر 2
+mines-per-tile = الألغام لكل خانة
+rule = قاعدة الأرقام
+rule-classic = كلاسيكية
+rule-liar = الكاذب
+rule-double = ألغام مضاعفة
+rule-negative = ألغام سالبة
+rule-orthogonal = المتعامدة فقط
+daily-salt = مجموعة التحدي اليومي
+daily-salt-placeholder = نفس النص، نفس اللوحات
+theme = السمة
+theme-system = مطابقة سطح المكتب
+theme-light = فاتحة
+theme-dark = داكنة
+skin = شكل الخانات
+skin-classic = كلاسيكي
+skin-flat = مسطح
+skin-high-contrast = تباين عالٍ
+skin-colorblind = مناسب لعمى الألوان
+tile-size = حجم الخانة
+new-game-confirm-title = بدء لعبة جديدة؟
+new-game-confirm-body = ستفقد اللعبة الحالية.
+paste-board-code = لصق رمز اللوحة
+daily-challenge = التحدي اليومي
+puzzles = الألغاز
+versus = لاعبان
+editor = محرر اللوحات
+editor-board = اللوحة
+editor-mode = النقر
+editor-mode-mines = يضع الألغام ويزيلها
+editor-mode-revealed = يكشف الخانات ويخفيها
+editor-width = العرض
+editor-height = الارتفاع
+editor-name = الاسم
+editor-name-placeholder = لغز بلا عنوان
+editor-file = الملف
+editor-file-placeholder = ينتهي بـ ‎.puzzle أو ‎.txt أو ‎.mbf أو ‎.json
+editor-check = تحقق
+editor-save = حفظ
+editor-close = إغلاق المحرر
+paste-board-code-body = العب لوحة رمز شاركه شخص آخر.
+board-code-invalid = هذا ليس رمز لوحة صالحًا، تحقق من نسخه بالكامل.
+board-code-placeholder = رمز اللوحة
+board-code-play = العب
+cancel = إلغاء
+tile-name = الصف { $row }، العمود { $column }
+tile-hidden = مخفية
+tile-flagged = { $count ->
+    [one] عليها علم
+    [two] عليها علمان
+   *[other] عليها { $count } أعلام
+}
+tile-unknown = معلمة كمجهولة
+tile-found = { $count ->
+    [one] لغم وجده اللاعب { $player }
+   *[other] { $count } ألغام وجدها اللاعب { $player }
+}
+tile-mine = { $count ->
+    [one] لغم
+    [two] لغمان
+   *[other] { $count } ألغام
+}
+tile-revealed = مكشوفة، { $value ->
+    [zero] لا ألغام مجاورة
+    [one] لغم واحد مجاور
+    [two] لغمان مجاوران
+    [few] { $value } ألغام مجاورة
+   *[other] { $value } لغمًا مجاورًا
+}
+announce-tile = الصف { $row }، العمود { $column }: { $state }.
+hint-safe = الصف { $row }، العمود { $column } آمنة.
+hint-none = لا يمكن استنتاج خانة آمنة.
+mines-remaining = { $count ->
+    [zero] لم يتبق أي لغم
+    [one] تبقى لغم واحد
+    [two] تبقى لغمان
+    [few] تبقت { $count } ألغام
+   *[other] تبقى { $count } لغمًا
+}
+game-lost = انتهت اللعبة، لقد أصبت لغمًا.
+game-won = لقد فزت!
+welcome = مرحبًا بك في COSMIC! ✨
+field-mines = الألغام: { $flags }/{ $mines }
+layer = الطبقة { $layer } من { $layers }
+tile-in-layer = { $layer }، { $tile }
+tui-prompt = اختر إجراءً (x,y,action:[r,f,?]):
+tui-layers = بدّل الطبقات باستخدام < و >.
+tui-seed = البذرة: { $seed } (إصدار التوزيع { $placement })
+board-code = رمز اللوحة: { $code }
+daily-title = التحدي اليومي بتاريخ { $date }.
+puzzle-title = لغز: { $name }
+puzzle-needs-guess = { $name } (لا يمكن حله دون تخمين)
+puzzle-needs-guess-short = لا يمكن حله دون تخمين
+puzzle-broken = { $name } تالف: { $error }
+puzzles-empty = لم يتم العثور على ألغاز في { $dir }
+tui-puzzle-prompt = اختر لغزًا برقمه، أو q للخروج:
+solve-cleared = أزال الحلّال اللوحة دون تخمين.
+solve-stuck = توقف الحلّال بعد كشف { $revealed } من { $safe } خانة آمنة.
+replay-running = لا تزال اللعبة جارية بعد { $moves ->
+    [one] حركة واحدة.
+   *[other] { $moves } حركات.
+}
+bench-result = تم إنشاء { $games } لوحة في { $seconds } ثانية، { $rate } في الثانية.
+analyze-board = { $tiles } خانة، { $mines } لغم، { $density }% من الخانات ألغام.
+analyze-bbbv = 3BV: { $bbbv }، الفتحات: { $openings }
+simulate-result = { $board } مع { $mines } لغم: فوز في { $won } من { $games } لعبة ({ $rate }%)، { $guesses } تخمين لكل لعبة.
+simulate-bbbv = 3BV: من { $min } إلى { $max }، بمتوسط { $mean }
+simulate-speed = استغرق { $seconds } ثانية على { $threads ->
+    [one] خيط واحد
+   *[other] { $threads } خيوط
+}، { $rate } لعبة في الثانية.
+bot-result = { $bot }: فاز في { $won } من { $games } لعبة، خسر { $lost }، استسلم في { $resigned }، { $timeouts } تجاوزات للوقت، { $invalid } غير صالحة، { $moves } نقلة لكل لعبة.
+bot-invalid = البذرة { $seed }: { $reason }
+race-waiting = في انتظار اللاعبين الآخرين، انضم { $joined } من { $players }...
+race-started = بدأ السباق!
+race-player = { $name }: تم كشف { $cleared }%، { $deaths ->
+    [one] موت واحد
+   *[other] { $deaths } مرات موت
+}
+race-finished = { $name }: أنهى في { $time } ثانية، { $deaths ->
+    [one] موت واحد
+   *[other] { $deaths } مرات موت
+}
+race-left = غادر { $name } السباق
+race-over = انتهى السباق.
+race-restart = العودة إلى بداية اللوحة.
+race-default-name = لاعب
+versus-turn = دور اللاعب { $player }. الألغام الموجودة: { $first } مقابل { $second }، يفوز من يجد { $needed }.
+versus-found = وجد اللاعب { $player } لغمًا ويلعب مرة أخرى.
+versus-won = فاز اللاعب { $player } بـ { $score } ألغام!
+versus-draw = تعادل، تم العثور على كل الألغام.
+versus-legend = تظهر الألغام التي وجدها اللاعبون على شكل P1 و P2.
+editor-mines = { $count ->
+    [one] لغم واحد
+   *[other] { $count } ألغام
+}
+editor-unique = يمكن حل اللوحة دون تخمين.
+editor-needs-guess = لا يمكن حل اللوحة دون تخمين.
+editor-saved = تم الحفظ في { $path }.
+daily-stats = التحديات اليومية: لُعب { $played }، فاز { $won }، { $streak ->
+    [one] فوز يوم واحد متتالٍ.
+   *[other] فوز { $streak } أيام متتالية.
+}
+tui-lost = لقد مت :(
+tui-won = لقد فزت!
+tui-quitting = جارٍ الخروج...
+tui-confirm-quit = هل تريد الخروج حقًا؟
+tui-yes-no = (y/n):
+tui-yes = y
+tui-question-marks-disabled = علامات الاستفهام معطلة
+tui-row = الصف { $row }: { $tiles }
+tui-column = العمود { $column }: { $state }
+tui-columns = الأعمدة من { $start } إلى { $end }: { $state }
+error-outside-grid = الإحداثيات { $coords } خارج الشبكة
+error-index-outside = الفهرس خارج الحقل
+error-already-initialized = الحقل مهيأ مسبقًا
+error-input-length = المدخل قصير جدًا
+error-separator = الفواصل غير صحيحة
+error-nan = ليس رقمًا
+error-outside-coordinates = خارج شبكة الإحداثيات
+error-unknown-action = إجراء غير معروف
+error-language = لغة غير معروفة "{ $lang }"
+error-neighbourhood = "{ $value }" ليس نوع جوار ولا قائمة إزاحات مثل "1,0;0,1"
+error-hex-wrap = لا يمكن وصل الحافة العليا بالسفلى في اللوحات السداسية إلا بعدد زوجي من الصفوف، وليس { $height }
+error-numbers-too-large = قد يحيط بالخانة الواحدة ما يصل إلى { $mines } لغمًا، وهذا أكثر مما تستطيع الأرقام عدّه، استخدم ألغامًا أقل لكل خانة أو جوارًا أصغر
+error-placement = إصدار توزيع الألغام { $version } غير مدعوم، الإصدار الحالي هو { $current }
+error-mines-fit = يتسع الحقل لـ { $placed } فقط من أصل { $mines } ألغام
+error-board-code = ليس رمز لوحة صالحًا
+error-date = "{ $value }" ليس تاريخًا مثل 2024-12-31
+error-daily-future = لا يمكن لعب تحديات الأيام المستقبلية بعد
+error-stats-save = لا يوجد مجلد لحفظ الإحصائيات
+error-layout-size = صفوف اللوحة مفقودة أو ليست بنفس الطول
+error-layout-character = "{ $character }" ليس لغمًا (*) ولا خانة فارغة (.)
+error-layout-json = ليس تخطيط لوحة مثل {"{"}"width": 9, "height": 9, "mines": [[0, 1]]{"}"}
+error-mbf-duplicate = اللغم في الصف { $row }، العمود { $column } مذكور أكثر من مرة
+error-puzzle-character = "{ $character }" ليس لغمًا (*) ولا خانة مخفية (.) ولا خانة مكشوفة (o) ولا رقمًا
+error-puzzle-number = الرقم في الصف { $row }، العمود { $column } لا يطابق الألغام حوله
+error-puzzle-dir = لا يوجد مجلد للبحث عن الألغاز فيه
+error-puzzle-choice = اختر رقمًا من 1 إلى { $count }
+error-puzzle-revealed-mine = لا يمكن كشف الخانة في الصف { $row }، العمود { $column }، فهي تحتوي على لغم
+error-coordinates = "{ $value }" ليست خانة مثل 3,4
+error-move = "{ $value }" ليست حركة مثل 3,4,r
+error-bench-board = تنشئ اختبارات الأداء اللوحات من الإعدادات، ولا يمكنها استخدام رمز أو تحدٍ يومي أو ملف
+error-thread = تعطل أحد خيوط العمل
+error-bot-command = يحتاج الروبوت إلى أمر لتشغيله
+error-bot-start = فشل تشغيل الروبوت "{ $command }": { $error }
+error-race-board = تحتاج السباقات إلى لوحة من الإعدادات أو رمز أو تحدٍ يومي، لا يمكن السباق على لوحات من ملفات
+error-race-host = فشلت استضافة سباق على { $addr }: { $error }
+error-race-connect = فشل الانضمام إلى السباق على { $addr }: { $error }
+error-race-closed = انقطع الاتصال بالمضيف
+error-format-unsupported = هذا التنسيق يدعم فقط اللوحات المسطحة بلغم واحد لكل خانة
+error-config-save = تعذر حفظ الإعدادات: { $error }
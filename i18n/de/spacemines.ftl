@@ -0,0 +1,236 @@
+app-title = Spacemines
+about = Über
+new-game = Neu
+seed = Seed
+undo = Rückgängig
+hint = Tipp
+options = Optionen
+view = Ansicht
+zoom-in = Vergrößern
+zoom-out = Verkleinern
+fit-window = An Fenster anpassen
+settings = Einstellungen
+gameplay = Spiel
+appearance = Darstellung
+difficulty = Schwierigkeit
+beginner = Anfänger
+intermediate = Fortgeschritten
+expert = Experte
+safe-start = Sicherer Start
+safe-start-tile = Angeklicktes Feld
+safe-start-relocate = Mine wird verschoben
+safe-start-opening = Garantierte Öffnung
+safe-start-no-guess = Ohne Raten lösbar
+question-marks = Fragezeichen
+confirm-prompts = Bestätigungsdialoge
+topology = Spielfeldform
+topology-rectangle = Rechteck
+topology-cylinder = Zylinder
+topology-torus = Torus
+topology-cylinder-edges = Der linke und rechte Rand sind verbunden.
+topology-torus-edges = Alle Ränder sind verbunden.
+grid = Feldform
+grid-square = Quadrat
+grid-hex = Sechseck
+grid-hex-rows = Die Felder sind Sechsecke, jede zweite Reihe ist um ein halbes Feld nach rechts verschoben.
+neighbourhood-von-neumann-rule = Zahlen zählen nur die Felder darüber, darunter, links und rechts.
+neighbourhood-knight-rule = Zahlen zählen die Felder, die ein Springer im Schach erreichen könnte.
+neighbourhood-radius-2-rule = Zahlen zählen alle Felder bis zu zwei Schritte entfernt.
+neighbourhood-custom-rule = Zahlen zählen { $count ->
+    [one] 1 eigenen Nachbarn
+   *[other] { $count } eigene Nachbarn
+}.
+mines-per-tile-rule = Jedes Feld kann bis zu { $count } Minen enthalten, die Zahlen zählen alle davon.
+rule-liar-legend = Jede Zahl lügt, die echte Anzahl ist genau eins mehr oder eins weniger.
+rule-double-legend = Jede Mine zählt doppelt.
+rule-negative-legend = Manche Minen zählen als minus eins, daher können Zahlen negativ sein.
+rule-orthogonal-legend = Zahlen zählen nur die Felder darüber, darunter, links und rechts.
+layers = Ebenen
+neighbourhood = Nachbarn
+neighbourhood-moore = Alle angrenzenden Felder
+neighbourhood-von-neumann = Orthogonale Felder
+neighbourhood-knight = Springerzüge
+neighbourhood-radius-2 = Radius 2
+mines-per-tile = Minen pro Feld
+rule = Zahlenregel
+rule-classic = Klassisch
+rule-liar = Lügner
+rule-double = Doppelte Minen
+rule-negative = Negative Minen
+rule-orthogonal = Nur orthogonal
+daily-salt = Gruppe für tägliche Herausforderungen
+daily-salt-placeholder = Gleicher Text, gleiche Spielfelder
+theme = Farbschema
+theme-system = Wie Desktop
+theme-light = Hell
+theme-dark = Dunkel
+skin = Feld-Design
+skin-classic = Klassisch
+skin-flat = Flach
+skin-high-contrast = Hoher Kontrast
+skin-colorblind = Farbenblind-freundlich
+tile-size = Feldgröße
+new-game-confirm-title = Neues Spiel starten?
+new-game-confirm-body = Das aktuelle Spiel geht verloren.
+paste-board-code = Spielfeldcode einfügen
+daily-challenge = Tägliche Herausforderung
+puzzles = Rätsel
+versus = Zwei Spieler
+editor = Spielfeld-Editor
+editor-board = Spielfeld
+editor-mode = Klicken
+editor-mode-mines = Setzt und entfernt Minen
+editor-mode-revealed = Deckt Felder auf und zu
+editor-width = Breite
+editor-height = Höhe
+editor-name = Name
+editor-name-placeholder = Unbenanntes Rätsel
+editor-file = Datei
+editor-file-placeholder = Endung .puzzle, .txt, .mbf oder .json
+editor-check = Prüfen
+editor-save = Speichern
+editor-close = Editor schließen
+paste-board-code-body = Das Spielfeld eines geteilten Codes spielen.
+board-code-invalid = Das ist kein gültiger Spielfeldcode, prüfe, ob er vollständig kopiert wurde.
+board-code-placeholder = Spielfeldcode
+board-code-play = Spielen
+cancel = Abbrechen
+tile-name = Zeile { $row }, Spalte { $column }
+tile-hidden = verdeckt
+tile-flagged = { $count ->
+    [one] markiert
+   *[other] { $count } Flaggen
+}
+tile-unknown = als unbekannt markiert
+tile-found = { $count ->
+    [one] Mine, gefunden von Spieler { $player }
+   *[other] { $count } Minen, gefunden von Spieler { $player }
+}
+tile-mine = { $count ->
+    [one] Mine
+   *[other] { $count } Minen
+}
+tile-revealed = aufgedeckt, { $value ->
+    [one] 1 benachbarte Mine
+   *[other] { $value } benachbarte Minen
+}
+announce-tile = Zeile { $row }, Spalte { $column }: { $state }.
+hint-safe = Zeile { $row }, Spalte { $column } ist sicher.
+hint-none = Es lässt sich kein sicheres Feld ableiten.
+mines-remaining = { $count ->
+    [one] 1 Mine übrig
+   *[other] { $count } Minen übrig
+}
+game-lost = Spiel vorbei, du hast eine Mine getroffen.
+game-won = Du hast gewonnen!
+welcome = Willkommen bei COSMIC! ✨
+field-mines = Minen: { $flags }/{ $mines }
+layer = Ebene { $layer } von { $layers }
+tile-in-layer = { $layer }, { $tile }
+tui-prompt = Aktion wählen (x,y,Aktion:[r,f,?]):
+tui-layers = Mit < und > die Ebene wechseln.
+tui-seed = Seed: { $seed } (Platzierungsversion { $placement })
+board-code = Spielfeldcode: { $code }
+daily-title = Tägliche Herausforderung vom { $date }.
+puzzle-title = Rätsel: { $name }
+puzzle-needs-guess = { $name } (nicht ohne Raten lösbar)
+puzzle-needs-guess-short = Nicht ohne Raten lösbar
+puzzle-broken = { $name } ist fehlerhaft: { $error }
+puzzles-empty = Keine Rätsel in { $dir } gefunden
+tui-puzzle-prompt = Rätsel per Nummer wählen, oder q zum Beenden:
+solve-cleared = Der Löser hat das Spielfeld ohne Raten geräumt.
+solve-stuck = Der Löser kam nach { $revealed } von { $safe } sicheren Feldern nicht weiter.
+replay-running = Das Spiel läuft nach { $moves ->
+    [one] 1 Zug
+   *[other] { $moves } Zügen
+} noch.
+bench-result = { $games } Spielfelder in { $seconds } Sekunden erzeugt, { $rate } pro Sekunde.
+analyze-board = { $tiles } Felder, { $mines } Minen, { $density } % der Felder sind Minen.
+analyze-bbbv = 3BV: { $bbbv }, Öffnungen: { $openings }
+simulate-result = { $board } mit { $mines } Minen: { $won } von { $games } Spielen gewonnen ({ $rate } %), { $guesses } Rateversuche pro Spiel.
+simulate-bbbv = 3BV: { $min } bis { $max }, im Schnitt { $mean }
+simulate-speed = Dauerte { $seconds } Sekunden mit { $threads ->
+    [one] 1 Thread
+   *[other] { $threads } Threads
+}, { $rate } Spiele pro Sekunde.
+bot-result = { $bot }: { $won } von { $games } Spielen gewonnen, { $lost } verloren, { $resigned } aufgegeben, { $timeouts } Zeitüberschreitungen, { $invalid } ungültig, { $moves } Züge pro Spiel.
+bot-invalid = Seed { $seed }: { $reason }
+race-waiting = Warte auf die anderen Spieler, { $joined } von { $players } sind beigetreten...
+race-started = Das Rennen hat begonnen!
+race-player = { $name }: { $cleared }% aufgedeckt, { $deaths ->
+    [one] 1 Tod
+   *[other] { $deaths } Tode
+}
+race-finished = { $name }: fertig nach { $time } Sekunden, { $deaths ->
+    [one] 1 Tod
+   *[other] { $deaths } Tode
+}
+race-left = { $name } hat das Rennen verlassen
+race-over = Das Rennen ist vorbei.
+race-restart = Zurück zum Anfang des Spielfelds.
+race-default-name = Spieler
+versus-turn = Spieler { $player } ist am Zug. Gefundene Minen: { $first } zu { $second }, { $needed } gewinnen.
+versus-found = Spieler { $player } hat eine Mine gefunden und ist nochmal dran.
+versus-won = Spieler { $player } gewinnt mit { $score } Minen!
+versus-draw = Unentschieden, alle Minen wurden gefunden.
+versus-legend = Von den Spielern gefundene Minen werden als P1 und P2 angezeigt.
+editor-mines = { $count ->
+    [one] 1 Mine
+   *[other] { $count } Minen
+}
+editor-unique = Das Spielfeld ist ohne Raten lösbar.
+editor-needs-guess = Das Spielfeld ist nicht ohne Raten lösbar.
+editor-saved = Gespeichert in { $path }.
+daily-stats = Tägliche Herausforderungen: { $played } gespielt, { $won } gewonnen, { $streak ->
+    [one] 1 Tag in Folge gewonnen.
+   *[other] { $streak } Tage in Folge gewonnen.
+}
+tui-lost = Du bist gestorben :(
+tui-won = Gewonnen!
+tui-quitting = Beende...
+tui-confirm-quit = Wirklich beenden?
+tui-yes-no = (j/n):
+tui-yes = j
+tui-question-marks-disabled = Fragezeichen sind deaktiviert
+tui-row = Zeile { $row }: { $tiles }
+tui-column = Spalte { $column }: { $state }
+tui-columns = Spalten { $start } bis { $end }: { $state }
+error-outside-grid = die Koordinaten { $coords } liegen außerhalb des Spielfelds
+error-index-outside = Index außerhalb des Spielfelds
+error-already-initialized = Spielfeld ist bereits initialisiert
+error-input-length = Eingabe zu kurz
+error-separator = nicht richtig getrennt
+error-nan = keine Zahl
+error-outside-coordinates = Außerhalb des Koordinatengitters
+error-unknown-action = unbekannte Aktion
+error-language = unbekannte Sprache „{ $lang }“
+error-neighbourhood = „{ $value }“ ist weder eine Nachbarschaft noch eine Liste von Verschiebungen wie „1,0;0,1“
+error-hex-wrap = Sechseckfelder können nur mit einer geraden Zahl von Zeilen oben und unten verbunden werden, nicht mit { $height }
+error-numbers-too-large = um ein einzelnes Feld könnten bis zu { $mines } Minen liegen, mehr als die Zahlen zählen können, nimm weniger Minen pro Feld oder eine kleinere Nachbarschaft
+error-placement = Version { $version } der Minenplatzierung wird nicht unterstützt, die aktuelle Version ist { $current }
+error-mines-fit = nur { $placed } von { $mines } Minen passen auf das Feld
+error-board-code = kein gültiger Spielfeldcode
+error-date = „{ $value }“ ist kein Datum wie 2024-12-31
+error-daily-future = tägliche Herausforderungen zukünftiger Tage können noch nicht gespielt werden
+error-stats-save = kein Verzeichnis zum Speichern der Statistiken
+error-layout-size = die Zeilen des Spielfelds fehlen oder sind nicht gleich lang
+error-layout-character = „{ $character }“ ist weder eine Mine (*) noch ein leeres Feld (.)
+error-layout-json = kein Spielfeld wie {"{"}"width": 9, "height": 9, "mines": [[0, 1]]{"}"}
+error-mbf-duplicate = die Mine in Zeile { $row }, Spalte { $column } ist mehrfach aufgeführt
+error-puzzle-character = „{ $character }“ ist weder eine Mine (*), ein verdecktes Feld (.), ein aufgedecktes Feld (o) noch eine Zahl
+error-puzzle-number = die Zahl in Zeile { $row }, Spalte { $column } passt nicht zu den Minen um sie herum
+error-puzzle-dir = kein Verzeichnis, in dem nach Rätseln gesucht werden kann
+error-puzzle-choice = wähle eine Zahl von 1 bis { $count }
+error-puzzle-revealed-mine = das Feld in Zeile { $row }, Spalte { $column } kann nicht aufgedeckt sein, es enthält eine Mine
+error-coordinates = „{ $value }“ ist kein Feld wie 3,4
+error-move = „{ $value }“ ist kein Zug wie 3,4,r
+error-bench-board = Benchmarks erzeugen Spielfelder aus den Einstellungen, sie können keinen Code, keine tägliche Herausforderung und keine Datei verwenden
+error-thread = ein Arbeits-Thread ist abgestürzt
+error-bot-command = ein Bot braucht einen Befehl, der ihn startet
+error-bot-start = der Bot „{ $command }“ konnte nicht gestartet werden: { $error }
+error-race-board = Rennen brauchen ein Spielfeld aus den Einstellungen, einem Code oder einer täglichen Herausforderung, Layouts aus Dateien gehen nicht
+error-race-host = das Rennen auf { $addr } konnte nicht gestartet werden: { $error }
+error-race-connect = dem Rennen auf { $addr } konnte nicht beigetreten werden: { $error }
+error-race-closed = die Verbindung zum Host ist abgebrochen
+error-format-unsupported = das Format unterstützt nur flache Spielfelder mit einer Mine pro Feld
+error-config-save = Einstellungen konnten nicht gespeichert werden: { $error }
@@ -0,0 +1,236 @@
+app-title = Spacemines
+about = About
+new-game = New
+seed = Seed
+undo = Undo
+hint = Hint
+options = Options
+view = View
+zoom-in = Zoom in
+zoom-out = Zoom out
+fit-window = Fit to window
+settings = Settings
+gameplay = Gameplay
+appearance = Appearance
+difficulty = Difficulty
+beginner = Beginner
+intermediate = Intermediate
+expert = Expert
+safe-start = Safe start
+safe-start-tile = Clicked tile
+safe-start-relocate = Mine moved away
+safe-start-opening = Guaranteed opening
+safe-start-no-guess = No guessing needed
+question-marks = Question marks
+confirm-prompts = Confirmation prompts
+topology = Board shape
+topology-rectangle = Rectangle
+topology-cylinder = Cylinder
+topology-torus = Torus
+topology-cylinder-edges = The left and right edges are connected.
+topology-torus-edges = All edges are connected.
+grid = Tile shape
+grid-square = Square
+grid-hex = Hexagon
+grid-hex-rows = Tiles are hexagons, every second row is shifted right by half a tile.
+neighbourhood-von-neumann-rule = Numbers only count the tiles above, below, left and right.
+neighbourhood-knight-rule = Numbers count the tiles a chess knight could jump to.
+neighbourhood-radius-2-rule = Numbers count every tile up to two steps away.
+neighbourhood-custom-rule = Numbers count { $count ->
+    [one] 1 custom neighbour
+   *[other] { $count } custom neighbours
+}.
+mines-per-tile-rule = Every tile can hold up to { $count } mines, numbers count all of them.
+rule-liar-legend = Every number lies, the real count is exactly one more or one less.
+rule-double-legend = Every mine counts twice.
+rule-negative-legend = Some mines count as minus one, so numbers can be negative.
+rule-orthogonal-legend = Numbers only count the tiles above, below, left and right.
+layers = Layers
+neighbourhood = Neighbours
+neighbourhood-moore = All touching tiles
+neighbourhood-von-neumann = Orthogonal tiles
+neighbourhood-knight = Knight's moves
+neighbourhood-radius-2 = Radius 2
+mines-per-tile = Mines per tile
+rule = Number rule
+rule-classic = Classic
+rule-liar = Liar
+rule-double = Double mines
+rule-negative = Negative mines
+rule-orthogonal = Orthogonal only
+daily-salt = Daily challenge group
+daily-salt-placeholder = Same text, same boards
+theme = Theme
+theme-system = Match desktop
+theme-light = Light
+theme-dark = Dark
+skin = Tile skin
+skin-classic = Classic
+skin-flat = Flat
+skin-high-contrast = High contrast
+skin-colorblind = Colorblind friendly
+tile-size = Tile size
+new-game-confirm-title = Start a new game?
+new-game-confirm-body = The current game will be lost.
+paste-board-code = Paste board code
+daily-challenge = Daily challenge
+puzzles = Puzzles
+versus = Two players
+editor = Board editor
+editor-board = Board
+editor-mode = Clicking
+editor-mode-mines = Places and removes mines
+editor-mode-revealed = Reveals and hides tiles
+editor-width = Width
+editor-height = Height
+editor-name = Name
+editor-name-placeholder = Untitled puzzle
+editor-file = File
+editor-file-placeholder = Ending in .puzzle, .txt, .mbf or .json
+editor-check = Check
+editor-save = Save
+editor-close = Close editor
+paste-board-code-body = Play the board of a code shared by someone else.
+board-code-invalid = This is not a valid board code, check that it was copied completely.
+board-code-placeholder = Board code
+board-code-play = Play
+cancel = Cancel
+tile-name = Row { $row }, column { $column }
+tile-hidden = hidden
+tile-flagged = { $count ->
+    [one] flagged
+   *[other] { $count } flags
+}
+tile-unknown = marked as unknown
+tile-found = { $count ->
+    [one] mine found by player { $player }
+   *[other] { $count } mines found by player { $player }
+}
+tile-mine = { $count ->
+    [one] mine
+   *[other] { $count } mines
+}
+tile-revealed = revealed, { $value ->
+    [one] 1 mine adjacent
+   *[other] { $value } mines adjacent
+}
+announce-tile = Row { $row }, column { $column }: { $state }.
+hint-safe = Row { $row }, column { $column } is safe.
+hint-none = No safe tile can be deduced.
+mines-remaining = { $count ->
+    [one] 1 mine remaining
+   *[other] { $count } mines remaining
+}
+game-lost = Game over, you hit a mine.
+game-won = You won!
+welcome = Welcome to COSMIC! ✨
+field-mines = Mines: { $flags }/{ $mines }
+layer = Layer { $layer } of { $layers }
+tile-in-layer = { $layer }, { $tile }
+tui-prompt = Choose action (x,y,action:[r,f,?]):
+tui-layers = Switch layers with < and >.
+tui-seed = Seed: { $seed } (placement version { $placement })
+board-code = Board code: { $code }
+daily-title = Daily challenge of { $date }.
+puzzle-title = Puzzle: { $name }
+puzzle-needs-guess = { $name } (cannot be solved without guessing)
+puzzle-needs-guess-short = Cannot be solved without guessing
+puzzle-broken = { $name } is broken: { $error }
+puzzles-empty = No puzzles found in { $dir }
+tui-puzzle-prompt = Choose a puzzle by number, or q to quit:
+solve-cleared = The solver cleared the board without guessing.
+solve-stuck = The solver got stuck after revealing { $revealed } of { $safe } safe tiles.
+replay-running = The game is still running after { $moves ->
+    [one] 1 move.
+   *[other] { $moves } moves.
+}
+bench-result = Generated { $games } boards in { $seconds } seconds, { $rate } per second.
+analyze-board = { $tiles } tiles, { $mines } mines, { $density }% of the tiles are mines.
+analyze-bbbv = 3BV: { $bbbv }, openings: { $openings }
+simulate-result = { $board } with { $mines } mines: won { $won } of { $games } games ({ $rate }%), { $guesses } guesses per game.
+simulate-bbbv = 3BV: { $min } to { $max }, { $mean } on average
+simulate-speed = Took { $seconds } seconds on { $threads ->
+    [one] 1 thread
+   *[other] { $threads } threads
+}, { $rate } games per second.
+bot-result = { $bot }: won { $won } of { $games } games, lost { $lost }, resigned { $resigned }, { $timeouts } timeouts, { $invalid } invalid, { $moves } moves per game.
+bot-invalid = Seed { $seed }: { $reason }
+race-waiting = Waiting for the other players, { $joined } of { $players } joined...
+race-started = The race has started!
+race-player = { $name }: { $cleared }% cleared, { $deaths ->
+    [one] 1 death
+   *[other] { $deaths } deaths
+}
+race-finished = { $name }: finished in { $time } seconds, { $deaths ->
+    [one] 1 death
+   *[other] { $deaths } deaths
+}
+race-left = { $name } left the race
+race-over = The race is over.
+race-restart = Back to the start of the board.
+race-default-name = Player
+versus-turn = Player { $player }'s turn. Mines found: { $first } to { $second }, { $needed } win.
+versus-found = Player { $player } found a mine and goes again.
+versus-won = Player { $player } wins with { $score } mines!
+versus-draw = Draw, every mine was found.
+versus-legend = Mines found by the players are shown as P1 and P2.
+editor-mines = { $count ->
+    [one] 1 mine
+   *[other] { $count } mines
+}
+editor-unique = The board can be solved without guessing.
+editor-needs-guess = The board cannot be solved without guessing.
+editor-saved = Saved to { $path }.
+daily-stats = Daily challenges: { $played } played, { $won } won, { $streak ->
+    [one] won 1 day in a row.
+   *[other] won { $streak } days in a row.
+}
+tui-lost = You died :(
+tui-won = You Won!
+tui-quitting = Quitting...
+tui-confirm-quit = Really quit?
+tui-yes-no = (y/n):
+tui-yes = y
+tui-question-marks-disabled = Question marks are disabled
+tui-row = Row { $row }: { $tiles }
+tui-column = column { $column }: { $state }
+tui-columns = columns { $start } to { $end }: { $state }
+error-outside-grid = requested coordinates { $coords } are outside the grid
+error-index-outside = index outside field
+error-already-initialized = field already initialized
+error-input-length = input too short
+error-separator = not separated properly
+error-nan = not a number
+error-outside-coordinates = Outside coordinate grid
+error-unknown-action = unknown action
+error-language = unknown language "{ $lang }"
+error-neighbourhood = "{ $value }" is neither a neighbourhood nor a list of offsets like "1,0;0,1"
+error-hex-wrap = hex boards can only wrap from top to bottom with an even number of rows, not { $height }
+error-numbers-too-large = up to { $mines } mines could be around a single tile, which is more than the numbers can count, use fewer mines per tile or a smaller neighbourhood
+error-placement = mine placement version { $version } is not supported, the current version is { $current }
+error-mines-fit = only { $placed } of { $mines } mines fit onto the board
+error-board-code = not a valid board code
+error-date = "{ $value }" is not a date like 2024-12-31
+error-daily-future = daily challenges of future dates cannot be played yet
+error-stats-save = no directory to save the stats in
+error-layout-size = the rows of the board are missing or not of the same length
+error-layout-character = "{ $character }" is neither a mine (*) nor an empty tile (.)
+error-layout-json = not a board layout like {"{"}"width": 9, "height": 9, "mines": [[0, 1]]{"}"}
+error-mbf-duplicate = the mine in row { $row }, column { $column } is listed more than once
+error-puzzle-character = "{ $character }" is neither a mine (*), a hidden tile (.), a revealed tile (o) nor a number
+error-puzzle-number = the number in row { $row }, column { $column } does not match the mines around it
+error-puzzle-dir = no directory to look for puzzles in
+error-puzzle-choice = choose a number from 1 to { $count }
+error-puzzle-revealed-mine = the tile in row { $row }, column { $column } cannot be revealed, it holds a mine
+error-coordinates = "{ $value }" is not a tile like 3,4
+error-move = "{ $value }" is not a move like 3,4,r
+error-bench-board = benchmarks generate boards from the settings, they cannot use a code, daily challenge or file
+error-thread = a worker thread crashed
+error-bot-command = a bot needs a command to start it
+error-bot-start = failed to start the bot "{ $command }": { $error }
+error-race-board = races need a board from the settings, a code or a daily challenge, layouts from files cannot be raced
+error-race-host = failed to host a race on { $addr }: { $error }
+error-race-connect = failed to join the race at { $addr }: { $error }
+error-race-closed = the connection to the host was lost
+error-format-unsupported = the format only supports flat boards with one mine per tile
+error-config-save = failed to save the settings: { $error }
@@ -1,28 +1,392 @@
+use std::path::PathBuf;
+
 use atoi::atoi;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use rand::Rng;
 
+use crate::{
+	config::{Config, Difficulty},
+	daily::{self, Date},
+	error::Error,
+	field::{
+		code::BoardCode,
+		formats::{self, Format},
+		puzzle,
+		rules::Rule,
+		tile::Coordintes,
+		topology::{Geometry, Grid, Neighbourhood, Topology},
+		Field, SafeStart, PLACEMENT_VERSION,
+	},
+	fl,
+};
+
+/// Minesweeper with variants, played in the terminal or the COSMIC desktop
 #[derive(Parser, Debug)]
-#[command(version)]
+#[command(version, args_conflicts_with_subcommands = true)]
 pub struct Args {
+	// what to do, playing a game if not given
+	#[command(subcommand)]
+	pub command: Option<Command>,
+
+	// without a subcommand the options of play are given directly, see play
+	#[command(flatten)]
+	pub game: PlayArgs,
+
+	/// Language to use instead of the one from the environment, e.g. "de"
+	#[arg(long, short, global = true)]
+	pub lang: Option<String>,
+}
+
+// everything deciding the board, only taken by the subcommands building one
+#[derive(clap::Args, Debug, Clone)]
+pub struct BoardArgs {
+	/// Seed of the board, any text works, random if not given
 	#[arg(short, long)]
 	pub seed: Option<String>,
 
-	#[arg(long, short = 'x', default_value_t = 9)]
-	pub width: u8,
+	/// Board of a shared code, it replaces all other settings of the board
+	#[arg(long, value_parser = parse_code)]
+	pub code: Option<BoardCode>,
+
+	/// Daily challenge of today, or of an earlier date given as YYYY-MM-DD
+	#[arg(long, value_parser = parse_date, conflicts_with = "code")]
+	pub daily: Option<Option<Date>>,
+
+	/// Mixed into the daily challenges, groups using the same salt play the same boards
+	#[arg(long)]
+	pub daily_salt: Option<String>,
+
+	/// Board layout read from a file, see --format
+	#[arg(long, conflicts_with_all = ["code", "daily"])]
+	pub import: Option<PathBuf>,
+
+	/// Format of board files, guessed from the file extension if not given
+	#[arg(long, value_enum)]
+	pub format: Option<Format>,
+
+	/// Version of the mine placement the seed was shared with, defaults to the current one
+	#[arg(long)]
+	pub placement: Option<u8>,
+
+	/// Width of the board, falls back to the difficulty from the settings
+	#[arg(long, short = 'x')]
+	pub width: Option<u8>,
+
+	/// Height of the board, falls back to the difficulty from the settings
+	#[arg(long, short = 'y')]
+	pub height: Option<u8>,
+
+	/// Number of layers, more than 1 makes the board three dimensional
+	#[arg(long, short = 'z')]
+	pub layers: Option<u8>,
+
+	/// Number of mines, falls back to the difficulty from the settings
+	#[arg(long, short)]
+	pub mines: Option<u16>,
+
+	/// How many mines a single tile can hold
+	#[arg(long, value_parser = clap::value_parser!(u8).range(1..))]
+	pub mines_per_tile: Option<u8>,
+
+	/// Which tiles the first click keeps free of mines
+	#[arg(long, value_enum)]
+	pub safe_start: Option<SafeStart>,
+
+	/// Which edges of the board are connected to each other
+	#[arg(long, value_enum)]
+	pub topology: Option<Topology>,
+
+	/// Shape of the tiles
+	#[arg(long, value_enum)]
+	pub grid: Option<Grid>,
+
+	/// Which tiles count as neighbours, moore, von-neumann, knight, radius-2 or offsets like "1,0;0,1;-1,0;0,-1"
+	#[arg(long, value_parser = parse_neighbourhood)]
+	pub neighbourhood: Option<Neighbourhood>,
+
+	/// How the numbers are computed from the mines around a tile
+	#[arg(long, value_enum)]
+	pub rule: Option<Rule>,
+}
+
+// the board and everything only making sense while playing it
+#[derive(clap::Args, Debug, Clone)]
+pub struct PlayArgs {
+	#[command(flatten)]
+	pub board: BoardArgs,
+
+	/// Lists the puzzles in a directory to pick one from, defaults to the puzzles in the data directory
+	#[arg(long, conflicts_with_all = ["code", "daily", "import"])]
+	pub puzzles: Option<Option<PathBuf>>,
+
+	/// Hosts a race on an address like 0.0.0.0:7878 and plays in it, the others join with --join
+	#[arg(long, conflicts_with_all = ["import", "puzzles"])]
+	pub host: Option<String>,
+
+	/// Joins the race hosted at an address like 192.168.1.2:7878, the host decides the board
+	#[arg(long, conflicts_with_all = ["host", "code", "daily", "import", "puzzles"])]
+	pub join: Option<String>,
+
+	/// How many players the host waits for, including itself
+	#[arg(long, default_value_t = 2)]
+	pub players: usize,
+
+	/// Shown to the other players of a race, defaults to the user name
+	#[arg(long)]
+	pub name: Option<String>,
 
-	#[arg(long, short = 'y', default_value_t = 9)]
-	pub height: u8,
+	/// Two players take turns on the same board, finding a mine scores and gives another turn
+	#[arg(long, conflicts_with_all = ["host", "join", "puzzles"])]
+	pub versus: bool,
 
-	#[arg(long, short, default_value_t = 10)]
-	pub mines: u16,
+	/// Writes the layout of the board to a file as soon as it is decided
+	#[arg(long)]
+	pub export: Option<PathBuf>,
 
+	/// Plays in the terminal instead of a window
 	#[cfg(all(feature = "tui", feature = "gui"))]
 	#[arg(short, long)]
 	pub tui: bool,
+
+	/// Describes the board in words instead of drawing it, for use with screen readers
+	#[cfg(feature = "tui")]
+	#[arg(short, long)]
+	pub describe: bool,
+}
+
+/// Every subcommand except play runs without a user interface and can print its result as one line of JSON
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+	/// Plays a game, the same as giving no subcommand
+	Play(PlayArgs),
+
+	/// Prints the board with every mine shown, without playing it
+	Generate {
+		#[command(flatten)]
+		board: BoardArgs,
+
+		/// First click as column,row counted from 1, in the middle if not given
+		#[arg(long, value_parser = parse_coords)]
+		start: Option<Coordintes>,
+
+		/// Prints the result as one line of JSON
+		#[arg(long)]
+		json: bool,
+	},
+
+	/// Runs the solver on a board file, puzzles start from their revealed tiles, other boards from --start
+	Solve {
+		/// The board file to solve
+		file: PathBuf,
+
+		/// Format of the file, guessed from the file extension if not given
+		#[arg(long, value_enum)]
+		format: Option<Format>,
+
+		/// First click as column,row counted from 1, in the middle if not given
+		#[arg(long, value_parser = parse_coords)]
+		start: Option<Coordintes>,
+
+		/// Prints the result as one line of JSON
+		#[arg(long)]
+		json: bool,
+	},
+
+	/// Plays moves like "3,4,r" on the board and prints where they lead, the same as in the tui
+	Replay {
+		#[command(flatten)]
+		board: BoardArgs,
+
+		/// Moves as column,row,action counted from 1, the actions are r to reveal, f to flag, ? to mark as unknown and c to chord
+		moves: Vec<String>,
+
+		/// Prints the result as one line of JSON
+		#[arg(long)]
+		json: bool,
+	},
+
+	/// Prints the results of the daily challenges
+	Stats {
+		/// Prints the result as one line of JSON
+		#[arg(long)]
+		json: bool,
+	},
+
+	/// Generates boards with the current settings and measures how long it takes
+	Bench {
+		#[command(flatten)]
+		board: BoardArgs,
+
+		/// Number of boards to generate
+		#[arg(long, default_value_t = 1000)]
+		games: u32,
+
+		/// Prints the result as one line of JSON
+		#[arg(long)]
+		json: bool,
+	},
+
+	/// Plays games with the solver, guessing when stuck, and reports how well it does
+	///
+	/// Seeds count up from --seed, so runs with the same seed play the same boards
+	Simulate {
+		#[command(flatten)]
+		board: BoardArgs,
+
+		/// Number of games to play for every preset
+		#[arg(long, default_value_t = 1000)]
+		games: u32,
+
+		/// Presets to play, each on its own, the current settings if none is given
+		#[arg(long, value_enum)]
+		preset: Vec<Difficulty>,
+
+		/// Number of threads playing, all cores are used if not given
+		#[arg(long)]
+		threads: Option<usize>,
+
+		/// Prints the result as one line of JSON
+		#[arg(long)]
+		json: bool,
+	},
+
+	/// Lets programs play against the engine over stdin and stdout
+	///
+	/// Every bot plays the same boards, seeds count up from --seed. The protocol is described in bot.rs
+	Bot {
+		#[command(flatten)]
+		board: BoardArgs,
+
+		/// Command lines starting the bots, split at whitespace, like "python3 bot.py"
+		#[arg(required = true)]
+		bots: Vec<String>,
+
+		/// Number of games every bot plays
+		#[arg(long, default_value_t = 10)]
+		games: u32,
+
+		/// How long a bot may think about a move, in milliseconds
+		#[arg(long, default_value_t = 1000)]
+		time_limit: u64,
+
+		/// Prints the result as one line of JSON
+		#[arg(long)]
+		json: bool,
+	},
+
+	/// Prints numbers describing the board, like its 3BV
+	Analyze {
+		#[command(flatten)]
+		board: BoardArgs,
+
+		/// First click as column,row counted from 1, in the middle if not given
+		#[arg(long, value_parser = parse_coords)]
+		start: Option<Coordintes>,
+
+		/// Prints the result as one line of JSON
+		#[arg(long)]
+		json: bool,
+	},
 }
 
 impl Args {
+	// the options of the game to play, given with or without the play subcommand
+	pub fn play(&self) -> &PlayArgs {
+		match &self.command {
+			Some(Command::Play(play)) => play,
+			_ => &self.game,
+		}
+	}
+}
+
+impl BoardArgs {
+	// returns width, height, layers and number of mines, preferring the command line over the config
+	// the mines of the difficulty are per layer, so the density stays the same on three dimensional fields
+	pub fn dimensions(&self, config: &Config) -> (u8, u8, u8, u16) {
+		let (width, height, mines) = config.difficulty.dimensions();
+		let layers = self.layers.unwrap_or(config.layers).max(1);
+		(
+			self.width.unwrap_or(width),
+			self.height.unwrap_or(height),
+			layers,
+			self.mines.unwrap_or(mines * layers as u16),
+		)
+	}
+
+	pub fn safe_start(&self, config: &Config) -> SafeStart {
+		self.safe_start.unwrap_or(config.safe_start)
+	}
+
+	pub fn geometry(&self, config: &Config) -> Geometry {
+		Geometry {
+			grid: self.grid.unwrap_or(config.grid),
+			topology: self.topology.unwrap_or(config.topology),
+			neighbourhood: self
+				.neighbourhood
+				.clone()
+				.unwrap_or_else(|| config.neighbourhood.clone()),
+		}
+	}
+
+	pub fn mines_per_tile(&self, config: &Config) -> u8 {
+		self.mines_per_tile.unwrap_or(config.mines_per_tile)
+	}
+
+	pub fn rule(&self, config: &Config) -> Rule {
+		self.rule.unwrap_or(config.rule)
+	}
+
+	pub fn placement(&self) -> u8 {
+		self.placement.unwrap_or(PLACEMENT_VERSION)
+	}
+
+	// date of the daily challenge to play, if one was requested
+	pub fn daily(&self) -> Option<Date> {
+		self.daily.map(|d| d.unwrap_or_else(Date::today))
+	}
+
+	pub fn daily_salt(&self, config: &Config) -> String {
+		self.daily_salt
+			.clone()
+			.unwrap_or_else(|| config.daily_salt.clone())
+	}
+
+	/*
+	The field to play, from the daily challenge, a code, an imported layout or the settings, in that order.
+	Fields from the settings are not initialized yet, the others already have their mines
+	*/
+	pub fn field(&self, config: &Config) -> Result<Field, Error> {
+		if let Some(date) = self.daily() {
+			return daily::board(date, &self.daily_salt(config)).play();
+		}
+		if let Some(code) = &self.code {
+			return code.play();
+		}
+		if let Some(field) = self.import() {
+			return field;
+		}
+		self.new_field(config)
+	}
+
+	// an empty field from the settings, preferring the command line over the config
+	pub fn new_field(&self, config: &Config) -> Result<Field, Error> {
+		let (width, height, layers, mines) = self.dimensions(config);
+		let mut f = Field::new_3d(width, height, layers, mines);
+		f.set_safe_start(self.safe_start(config));
+		f.set_geometry(self.geometry(config));
+		f.set_mines_per_tile(self.mines_per_tile(config));
+		f.set_rule(self.rule(config));
+		f.set_placement(self.placement());
+		f.check()?;
+		Ok(f)
+	}
+
+	// the field from --import, if one was given
+	pub fn import(&self) -> Option<Result<Field, Error>> {
+		let path = self.import.as_ref()?;
+		Some(formats::import(path, self.format))
+	}
+
 	pub fn get_seed(&self) -> u64 {
 		let seed = match &self.seed {
 			None => {
@@ -41,12 +405,79 @@ impl Args {
 			return s;
 		}
 
-		let checksummer = crc::Crc::<u64>::new(&crc::CRC_64_ECMA_182);
-		checksummer.checksum(&char_bytes)
+		seed_from_text(&seed)
+	}
+}
+
+impl PlayArgs {
+	// directory of the puzzle browser, if it was requested
+	pub fn puzzle_dir(&self) -> Option<PathBuf> {
+		match &self.puzzles {
+			None => None,
+			Some(Some(dir)) => Some(dir.clone()),
+			Some(None) => puzzle::default_dir(),
+		}
+	}
+
+	// writes the field to the file from --export, only does something once the layout is decided
+	pub fn export(&self, field: &Field) {
+		let path = match &self.export {
+			Some(p) if field.is_initialized() => p,
+			_ => return,
+		};
+		if let Err(e) = formats::export(field, path, self.board.format) {
+			e.out();
+		}
 	}
 }
 
+// any text can be used as a seed, the same text always gives the same seed
+pub fn seed_from_text(text: &str) -> u64 {
+	let char_bytes: Vec<u8> = text.chars().map(|c| c as u8).collect();
+	let checksummer = crc::Crc::<u64>::new(&crc::CRC_64_ECMA_182);
+	checksummer.checksum(&char_bytes)
+}
+
 pub fn new_random_seed() -> u64 {
 	let mut rng = rand::thread_rng();
 	rng.gen()
 }
+
+// challenges of future days cannot be played early
+fn parse_date(value: &str) -> Result<Date, String> {
+	let date: Date = value.parse().map_err(|e: Error| e.message().to_string())?;
+	if date > Date::today() {
+		return Err(fl!("error-daily-future"));
+	}
+	Ok(date)
+}
+
+fn parse_code(value: &str) -> Result<BoardCode, String> {
+	value.parse().map_err(|e: Error| e.message().to_string())
+}
+
+// 1 based like in the tui, "3,4" is the third column of the fourth row
+pub fn parse_coords(value: &str) -> Result<Coordintes, String> {
+	let invalid = || fl!("error-coordinates", value = value);
+	let parts: Vec<&str> = value.split(',').map(str::trim).collect();
+	if parts.len() < 2 || parts.len() > 3 {
+		return Err(invalid());
+	}
+
+	let mut numbers = Vec::new();
+	for part in parts {
+		match part.parse::<u8>() {
+			Ok(n) if n > 0 => numbers.push(n - 1),
+			_ => return Err(invalid()),
+		}
+	}
+	Ok(Coordintes {
+		x: numbers[0],
+		y: numbers[1],
+		z: numbers.get(2).copied().unwrap_or(0),
+	})
+}
+
+fn parse_neighbourhood(value: &str) -> Result<Neighbourhood, String> {
+	value.parse().map_err(|e: Error| e.message().to_string())
+}
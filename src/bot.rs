@@ -0,0 +1,537 @@
+use std::{
+	io::{BufRead, BufReader, Write},
+	process::{Child, ChildStdin, Command, Stdio},
+	sync::mpsc::{self, Receiver, RecvTimeoutError},
+	thread,
+	time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+
+use crate::{
+	args::BoardArgs,
+	cli::visible_board,
+	config::Config,
+	error::Error,
+	field::{
+		moves::{self, Move, Progress},
+		tile::Coordintes,
+		topology::Neighbourhood,
+		Field,
+	},
+	fl,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/*
+The bot protocol, version 1.
+
+Every game starts the bot as a new process. The engine writes JSON objects to its stdin and reads
+the answers from its stdout, one object per line. What the bot writes to stderr is passed through.
+Coordinates count from 0, x is the column, y the row and z the layer.
+
+From the engine:
+	{"type":"hello","protocol":1,"game":1,"games":10,"width":9,"height":9,"layers":1,"mines":10,
+	"mines_per_tile":1,"grid":"square","topology":"rectangle","neighbourhood":"moore","rule":"classic",
+	"time_limit_ms":1000,"max_moves":324}
+		once at the start, not answered. Custom neighbourhoods are a list of [x, y] offsets
+	{"type":"board","move":0,"mines_remaining":10,"board":[[["hidden",1,"flag"]]]}
+		layers of rows of tiles, a tile is its number once revealed, otherwise "hidden", "flag", "unknown" or "mine".
+		The bot answers every board with exactly one action, within the time limit
+	{"type":"result","result":"won","moves":12,"seconds":0.25,"reason":null,"board":[...]}
+		once the game is over, then stdin is closed and the bot has to exit within the time limit
+
+From the bot:
+	{"action":"reveal","x":3,"y":4}
+		"flag", "unknown" and "chord" work the same way, "z" can be left out on flat boards.
+		Flags cycle through the counts if tiles hold several mines
+	{"action":"resign"}
+
+On boards from the settings the first reveal places the mines, keeping the revealed tile free of them
+unless there are more mines than fit into the other tiles. Boards from --code and --daily already have
+their mines and arrive with the starting tile revealed, boards from --import have their mines and nothing revealed,
+so on those every reveal can hit a mine. A reveal hitting a mine loses, the first one as well.
+
+A game is "won" once every safe tile is revealed and every mine carries as many flags as it holds mines,
+and "lost" by revealing a mine.
+The bot can also have "resigned", run into a "timeout", or have made an "invalid" move, which includes answers
+that are no action, tiles outside the board, more moves than max_moves and bots that stop early.
+The reason says what was invalid, it is null otherwise.
+
+The version only goes up for changes that break bots, new fields can show up in any version
+*/
+pub const PROTOCOL_VERSION: u8 = 1;
+
+// how a game of a bot ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+	Won,
+	Lost,
+	Resigned,
+	Timeout,
+	Invalid,
+}
+
+impl Outcome {
+	pub const ALL: [Outcome; 5] = [
+		Outcome::Won,
+		Outcome::Lost,
+		Outcome::Resigned,
+		Outcome::Timeout,
+		Outcome::Invalid,
+	];
+
+	// the name used by the protocol
+	pub fn name(&self) -> &'static str {
+		match self {
+			Outcome::Won => "won",
+			Outcome::Lost => "lost",
+			Outcome::Resigned => "resigned",
+			Outcome::Timeout => "timeout",
+			Outcome::Invalid => "invalid",
+		}
+	}
+}
+
+// what came back after asking the bot for a move
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+	Line(String),
+	Timeout,
+	// the bot exited or closed its output
+	Closed,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameReport {
+	pub seed: u64,
+	pub outcome: Outcome,
+	pub moves: u32,
+	pub seconds: f64,
+	pub reason: Option<String>,
+	pub board: Value,
+}
+
+impl GameReport {
+	// the last message of a game
+	pub fn message(&self) -> Value {
+		json!({
+			"type": "result",
+			"result": self.outcome.name(),
+			"moves": self.moves,
+			"seconds": self.seconds,
+			"reason": self.reason,
+			"board": self.board,
+		})
+	}
+}
+
+// every tile can only be revealed once, so this leaves plenty of room for flags
+fn max_moves(field: &Field) -> u32 {
+	(field.size() as u32).saturating_mul(4)
+}
+
+fn hello(field: &Field, game: u32, games: u32, time_limit: Duration) -> Value {
+	let limit = field.get_limit();
+	let geometry = field.get_geometry();
+	let name =
+		|value: Option<clap::builder::PossibleValue>| value.map(|v| v.get_name().to_string());
+	let neighbourhood = match &geometry.neighbourhood {
+		Neighbourhood::Moore => json!("moore"),
+		Neighbourhood::VonNeumann => json!("von-neumann"),
+		Neighbourhood::Knight => json!("knight"),
+		Neighbourhood::Radius2 => json!("radius-2"),
+		Neighbourhood::Custom(offsets) => {
+			json!(offsets.iter().map(|(x, y)| [*x, *y]).collect::<Vec<_>>())
+		}
+	};
+
+	json!({
+		"type": "hello",
+		"protocol": PROTOCOL_VERSION,
+		"game": game,
+		"games": games,
+		"width": limit.x,
+		"height": limit.y,
+		"layers": limit.z,
+		"mines": field.get_num_mines(),
+		"mines_per_tile": field.get_mines_per_tile(),
+		"grid": name(geometry.grid.to_possible_value()),
+		"topology": name(geometry.topology.to_possible_value()),
+		"neighbourhood": neighbourhood,
+		"rule": name(field.get_rule().to_possible_value()),
+		"time_limit_ms": time_limit.as_millis() as u64,
+		"max_moves": max_moves(field),
+	})
+}
+
+fn board_message(field: &Field, moves: u32) -> Value {
+	let remaining = field.get_num_mines() as i32 - field.num_flags() as i32;
+	json!({
+		"type": "board",
+		"move": moves,
+		"mines_remaining": remaining,
+		"board": visible_board(field),
+	})
+}
+
+// an answer of the bot, the coordinates are checked against the board afterwards
+#[derive(Deserialize)]
+struct Answered {
+	action: String,
+	x: Option<i64>,
+	y: Option<i64>,
+	z: Option<i64>,
+}
+
+// reasons are part of the protocol, so they are not translated
+// no move means the bot resigned
+fn parse_action(line: &str, limit: &Coordintes) -> Result<Option<(Move, Coordintes)>, String> {
+	let answer: Answered =
+		serde_json::from_str(line.trim()).map_err(|e| format!("not an action: {}", e))?;
+	let action = match answer.action.as_str() {
+		"reveal" => Move::Reveal,
+		"flag" => Move::Flag,
+		"unknown" => Move::Unknown,
+		"chord" => Move::Chord,
+		"resign" => return Ok(None),
+		other => return Err(format!("unknown action \"{}\"", other)),
+	};
+
+	let coordinate = |key: &str, value: Option<i64>, size: u8| match value {
+		Some(v) if (0..size as i64).contains(&v) => Ok(v as u8),
+		_ => Err(format!("\"{}\" is missing or outside the board", key)),
+	};
+	let coords = Coordintes {
+		x: coordinate("x", answer.x, limit.x)?,
+		y: coordinate("y", answer.y, limit.y)?,
+		z: coordinate("z", answer.z.or(Some(0)), limit.z)?,
+	};
+	Ok(Some((action, coords)))
+}
+
+/*
+Plays one game, asking for a move whenever the board changed, the same way the tui asks a player.
+Fields that are not initialized get their mines from the seed on the first reveal
+*/
+pub fn play_game(template: &Field, seed: u64, mut ask: impl FnMut(&Value) -> Answer) -> GameReport {
+	let mut field = template.clone();
+	let max_moves = max_moves(&field);
+	let started = Instant::now();
+	let mut moves = 0;
+
+	let mut progress = moves::progress(&field);
+	let (outcome, reason) = loop {
+		match progress {
+			Progress::Lost => break (Outcome::Lost, None),
+			Progress::Won => break (Outcome::Won, None),
+			Progress::Playing => (),
+		}
+		if moves >= max_moves {
+			break (Outcome::Invalid, Some("too many moves".to_string()));
+		}
+
+		let line = match ask(&board_message(&field, moves)) {
+			Answer::Line(l) => l,
+			Answer::Timeout => break (Outcome::Timeout, None),
+			Answer::Closed => break (Outcome::Invalid, Some("the bot stopped".to_string())),
+		};
+		let (action, coords) = match parse_action(&line, &field.get_limit()) {
+			Ok(Some(a)) => a,
+			Ok(None) => {
+				moves += 1;
+				break (Outcome::Resigned, None);
+			}
+			Err(reason) => break (Outcome::Invalid, Some(reason)),
+		};
+		moves += 1;
+
+		progress = match moves::play(&mut field, action, &coords, seed) {
+			Ok(p) => p,
+			Err(e) => break (Outcome::Invalid, Some(e.message().to_string())),
+		};
+	};
+
+	GameReport {
+		seed,
+		outcome,
+		moves,
+		seconds: started.elapsed().as_secs_f64(),
+		reason,
+		board: visible_board(&field),
+	}
+}
+
+// a running bot, its output is read on a thread of its own so waiting for an answer can time out
+struct Process {
+	child: Child,
+	input: Option<ChildStdin>,
+	lines: Receiver<String>,
+	time_limit: Duration,
+}
+
+impl Process {
+	// the command is split at whitespace, the first part is the program
+	fn start(command: &str, time_limit: Duration) -> Result<Process, Error> {
+		let mut parts = command.split_whitespace();
+		let program = parts
+			.next()
+			.ok_or_else(|| Error::new(&fl!("error-bot-command")))?;
+		let failed = |e: std::io::Error| {
+			Error::new(&fl!(
+				"error-bot-start",
+				command = command,
+				error = e.to_string()
+			))
+		};
+		let mut child = Command::new(program)
+			.args(parts)
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::inherit())
+			.spawn()
+			.map_err(failed)?;
+
+		let (sender, lines) = mpsc::channel();
+		if let Some(output) = child.stdout.take() {
+			thread::spawn(move || {
+				for line in BufReader::new(output).lines().map_while(Result::ok) {
+					if sender.send(line).is_err() {
+						break;
+					}
+				}
+			});
+		}
+
+		Ok(Process {
+			input: child.stdin.take(),
+			child,
+			lines,
+			time_limit,
+		})
+	}
+
+	fn send(&mut self, message: &Value) -> bool {
+		match &mut self.input {
+			None => false,
+			Some(input) => writeln!(input, "{}", message)
+				.and_then(|_| input.flush())
+				.is_ok(),
+		}
+	}
+
+	fn ask(&mut self, message: &Value) -> Answer {
+		if !self.send(message) {
+			return Answer::Closed;
+		}
+		match self.lines.recv_timeout(self.time_limit) {
+			Ok(line) => Answer::Line(line),
+			Err(RecvTimeoutError::Timeout) => Answer::Timeout,
+			Err(RecvTimeoutError::Disconnected) => Answer::Closed,
+		}
+	}
+
+	// closing stdin tells the bot to exit, it is killed if that takes longer than a move may
+	fn stop(mut self) {
+		self.input = None;
+		let deadline = Instant::now() + self.time_limit;
+		while Instant::now() < deadline {
+			if let Ok(Some(_)) = self.child.try_wait() {
+				return;
+			}
+			thread::sleep(Duration::from_millis(10));
+		}
+		_ = self.child.kill();
+		_ = self.child.wait();
+	}
+}
+
+// every bot plays the same boards, seeds count up from --seed like in the simulation
+pub fn run(
+	args: &BoardArgs,
+	config: &Config,
+	bots: &[String],
+	games: u32,
+	time_limit: u64,
+	json: bool,
+) -> Result<(), Error> {
+	let template = args.field(config)?;
+	let seed = args.get_seed();
+	let time_limit = Duration::from_millis(time_limit);
+
+	let mut results = Vec::new();
+	for command in bots {
+		let mut reports = Vec::new();
+		for game in 0..games {
+			let mut process = Process::start(command, time_limit)?;
+			// a bot that cannot take the hello is caught by the first question
+			process.send(&hello(&template, game + 1, games, time_limit));
+			let report = play_game(&template, seed.wrapping_add(game as u64), |m| {
+				process.ask(m)
+			});
+			process.send(&report.message());
+			process.stop();
+			reports.push(report);
+		}
+		results.push((command, reports));
+	}
+
+	if json {
+		let bots: Vec<Value> = results
+			.iter()
+			.map(|(command, reports)| {
+				let mut entry = json!({ "command": command });
+				for outcome in Outcome::ALL {
+					let count = reports.iter().filter(|r| r.outcome == outcome).count();
+					entry[outcome.name()] = json!(count);
+				}
+				let games: Vec<Value> = reports
+					.iter()
+					.enumerate()
+					.map(|(i, r)| {
+						json!({
+							"game": i + 1,
+							"seed": r.seed.to_string(),
+							"result": r.outcome.name(),
+							"moves": r.moves,
+							"seconds": r.seconds,
+							"reason": r.reason,
+						})
+					})
+					.collect();
+				entry["games"] = json!(games);
+				entry
+			})
+			.collect();
+		println!(
+			"{}",
+			json!({
+				"protocol": PROTOCOL_VERSION,
+				"seed": seed.to_string(),
+				"bots": bots,
+			})
+		);
+		return Ok(());
+	}
+
+	for (command, reports) in results {
+		let count = |outcome| reports.iter().filter(|r| r.outcome == outcome).count();
+		let moves: u32 = reports.iter().map(|r| r.moves).sum();
+		println!(
+			"{}",
+			fl!(
+				"bot-result",
+				bot = command.as_str(),
+				won = count(Outcome::Won),
+				games = reports.len(),
+				lost = count(Outcome::Lost),
+				resigned = count(Outcome::Resigned),
+				timeouts = count(Outcome::Timeout),
+				invalid = count(Outcome::Invalid),
+				moves = format!("{:.1}", moves as f64 / reports.len().max(1) as f64)
+			)
+		);
+		for report in reports.iter().filter(|r| r.reason.is_some()) {
+			println!(
+				"{}",
+				fl!(
+					"bot-invalid",
+					seed = report.seed.to_string(),
+					reason = report.reason.clone().unwrap_or_default()
+				)
+			);
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod bot_tests {
+	use super::*;
+
+	// answers with the given lines in order, then times out
+	fn scripted(lines: &[&str]) -> impl FnMut(&Value) -> Answer {
+		let mut lines: Vec<String> = lines.iter().rev().map(|l| l.to_string()).collect();
+		move |_| lines.pop().map(Answer::Line).unwrap_or(Answer::Timeout)
+	}
+
+	#[test]
+	fn test_games() -> Result<(), Error> {
+		let limit = Coordintes { x: 3, y: 3, z: 1 };
+		let corner = Field::from_layout(limit, &[1, 0, 0, 0, 0, 0, 0, 0, 0])?;
+
+		let won = play_game(
+			&corner,
+			0,
+			scripted(&[
+				r#"{"action":"flag","x":0,"y":0}"#,
+				r#"{"action":"reveal","x":2,"y":2,"z":0}"#,
+			]),
+		);
+		if won.outcome != Outcome::Won || won.moves != 2 || won.reason.is_some() {
+			return Err(Error::new("clearing the board did not win"));
+		}
+
+		let lost = play_game(
+			&corner,
+			0,
+			scripted(&[r#"{"action":"reveal","x":0,"y":0}"#]),
+		);
+		if lost.outcome != Outcome::Lost || lost.board.to_string().find("mine").is_none() {
+			return Err(Error::new("revealing the mine did not lose"));
+		}
+
+		let broken = [
+			"reveal 1 1",
+			r#"{"action":"jump","x":0,"y":0}"#,
+			r#"{"action":"reveal","x":3,"y":0}"#,
+			r#"{"action":"reveal","x":0}"#,
+			r#"{"action":"reveal","x":0,"y":0,"z":1}"#,
+		];
+		for line in broken {
+			let invalid = play_game(&corner, 0, scripted(&[line]));
+			if invalid.outcome != Outcome::Invalid || invalid.reason.is_none() {
+				return Err(Error::new(&format!("accepted {}", line)));
+			}
+		}
+
+		if play_game(&corner, 0, scripted(&[])).outcome != Outcome::Timeout {
+			return Err(Error::new("waiting forever"));
+		}
+		if play_game(&corner, 0, |_| Answer::Closed).outcome != Outcome::Invalid {
+			return Err(Error::new("stopped bot kept playing"));
+		}
+
+		// flagging back and forth cannot go on forever
+		let flags = play_game(&corner, 0, |_| {
+			Answer::Line(r#"{"action":"flag","x":1,"y":1}"#.to_string())
+		});
+		if flags.outcome != Outcome::Invalid || flags.moves != max_moves(&corner) {
+			return Err(Error::new("too many moves allowed"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_first_reveal() -> Result<(), Error> {
+		// the first reveal is safe on boards from the settings, and the same seed places the same mines
+		let template = Field::new(9, 9, 10);
+		let first = r#"{"action":"reveal","x":4,"y":4}"#;
+		let a = play_game(&template, 7, scripted(&[first, r#"{"action":"resign"}"#]));
+		let b = play_game(&template, 7, scripted(&[first, r#"{"action":"resign"}"#]));
+		if a.outcome != Outcome::Resigned || a.moves != 2 || a.board != b.board {
+			return Err(Error::new("first reveal played wrongly"));
+		}
+
+		// without room for a free tile even the first reveal loses
+		let corner = r#"{"action":"reveal","x":0,"y":0}"#;
+		let full = play_game(&Field::new(2, 1, 2), 7, scripted(&[corner]));
+		if full.outcome != Outcome::Lost || full.moves != 1 {
+			return Err(Error::new("first reveal on a full board did not lose"));
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,531 @@
+use std::{thread, time::Instant};
+
+use clap::ValueEnum;
+
+use crate::{
+	args::{parse_coords, BoardArgs, Command},
+	bot,
+	config::{Config, Difficulty},
+	daily::{DailyStats, Date},
+	error::Error,
+	field::{
+		analysis::analyze,
+		formats::{self, Format},
+		moves::{self, Move, Progress},
+		puzzle::{self, Puzzle},
+		simulation::{self, Summary},
+		solver,
+		tile::{Coordintes, Tile},
+		Field,
+	},
+	fl,
+};
+use serde_json::{json, Map, Value};
+
+// runs a subcommand without a user interface, the output goes to stdout
+pub fn run(command: &Command) -> Result<(), Error> {
+	let config = Config::load();
+	match command {
+		Command::Play(_) => Ok(()),
+		Command::Generate { board, start, json } => generate(board, &config, *start, *json),
+		Command::Solve {
+			file,
+			format,
+			start,
+			json,
+		} => solve(file, *format, *start, *json),
+		Command::Replay { board, moves, json } => replay(board, &config, moves, *json),
+		Command::Stats { json } => stats(*json),
+		Command::Bench { board, games, json } => bench(board, &config, *games, *json),
+		Command::Simulate {
+			board,
+			games,
+			preset,
+			threads,
+			json,
+		} => simulate(board, &config, *games, preset, *threads, *json),
+		Command::Bot {
+			board,
+			bots,
+			games,
+			time_limit,
+			json,
+		} => bot::run(board, &config, bots, *games, *time_limit, *json),
+		Command::Analyze { board, start, json } => analyze_board(board, &config, *start, *json),
+	}
+}
+
+// the middle of the first layer, where boards are started if no tile is given
+fn middle(field: &Field) -> Coordintes {
+	let limit = field.get_limit();
+	Coordintes {
+		x: limit.x / 2,
+		y: limit.y / 2,
+		z: 0,
+	}
+}
+
+// the board of the arguments with the first click made, boards from codes already come with theirs
+fn started_board(
+	args: &BoardArgs,
+	config: &Config,
+	start: Option<Coordintes>,
+) -> Result<Field, Error> {
+	let mut field = args.field(config)?;
+	let start = start.unwrap_or_else(|| middle(&field));
+	if !field.is_initialized() {
+		field.init(&start, args.get_seed())?;
+		field.reveal(&start)?;
+	} else if field.get_field().iter().all(|t| !t.revealed) {
+		field.reveal(&start)?;
+	}
+	Ok(field)
+}
+
+// mines as [x, y] or [x, y, z] counted from 0, the same as the json layout format so the output can be imported again
+fn mine_list(field: &Field) -> Value {
+	let limit = field.get_limit();
+	let mut mines = Vec::new();
+	for (i, tile) in field.get_field().iter().enumerate() {
+		let x = i % limit.x as usize;
+		let y = i / limit.x as usize % limit.y as usize;
+		let z = i / (limit.x as usize * limit.y as usize);
+		for _ in 0..tile.mines {
+			let coords = if limit.z > 1 {
+				vec![x, y, z]
+			} else {
+				vec![x, y]
+			};
+			mines.push(coords);
+		}
+	}
+	json!(mines)
+}
+
+// what a player sees of a tile
+fn visible_tile(tile: &Tile, lost: bool) -> Value {
+	if tile.revealed && !tile.is_mine {
+		json!(tile.shown)
+	} else if tile.is_mine && (tile.revealed || lost) {
+		json!("mine")
+	} else if tile.flag {
+		json!("flag")
+	} else if tile.unknown {
+		json!("unknown")
+	} else {
+		json!("hidden")
+	}
+}
+
+// the board as a player sees it, as layers of rows of tiles
+pub fn visible_board(field: &Field) -> Value {
+	let limit = field.get_limit();
+	let tiles = field.get_field();
+	let row_length = limit.x.max(1) as usize;
+	let layer_length = row_length * limit.y.max(1) as usize;
+
+	let layers: Vec<Vec<Vec<Value>>> = tiles
+		.chunks(layer_length)
+		.map(|layer| {
+			layer
+				.chunks(row_length)
+				.map(|row| {
+					row.iter()
+						.map(|t| visible_tile(t, field.has_lost()))
+						.collect()
+				})
+				.collect()
+		})
+		.collect();
+	json!(layers)
+}
+
+fn state(field: &Field) -> &'static str {
+	if field.has_lost() {
+		"lost"
+	} else if field.is_initialized() && field.victory() {
+		"won"
+	} else {
+		"running"
+	}
+}
+
+// seeds do not fit into a double exactly, so they are written as strings
+fn seed_and_code(field: &Field) -> (Value, Value) {
+	match field.board_code() {
+		None => (Value::Null, Value::Null),
+		Some(code) => (json!(code.seed.to_string()), json!(code.to_string())),
+	}
+}
+
+fn generate(
+	args: &BoardArgs,
+	config: &Config,
+	start: Option<Coordintes>,
+	json: bool,
+) -> Result<(), Error> {
+	let field = started_board(args, config, start)?;
+	let limit = field.get_limit();
+
+	if json {
+		let (seed, code) = seed_and_code(&field);
+		println!(
+			"{}",
+			json!({
+				"width": limit.x,
+				"height": limit.y,
+				"layers": limit.z,
+				"mines": mine_list(&field),
+				"seed": seed,
+				"code": code,
+			})
+		);
+		return Ok(());
+	}
+
+	if let Some(code) = field.board_code() {
+		println!(
+			"{}",
+			fl!(
+				"tui-seed",
+				seed = code.seed.to_string(),
+				placement = code.placement
+			)
+		);
+		println!("{}", fl!("board-code", code = code.to_string()));
+	}
+	field.print_revealed();
+	Ok(())
+}
+
+fn solve(
+	file: &std::path::Path,
+	format: Option<Format>,
+	start: Option<Coordintes>,
+	json: bool,
+) -> Result<(), Error> {
+	let field = if file.extension().is_some_and(|e| e == puzzle::EXTENSION) {
+		Puzzle::load(file)?.field
+	} else {
+		let mut field = formats::import(file, format)?;
+		let start = start.unwrap_or_else(|| middle(&field));
+		field.reveal(&start)?;
+		field
+	};
+
+	let solved = solver::solve_as_far(field);
+	let safe = solved.get_field().iter().filter(|t| !t.is_mine).count();
+	let revealed = solved
+		.get_field()
+		.iter()
+		.filter(|t| t.revealed && !t.is_mine)
+		.count();
+	let cleared = !solved.has_lost() && revealed == safe;
+
+	if json {
+		println!(
+			"{}",
+			json!({
+				"solved": cleared,
+				"revealed": revealed,
+				"safe": safe,
+				"board": visible_board(&solved),
+			})
+		);
+		return Ok(());
+	}
+
+	println!("{}", solved);
+	if cleared {
+		println!("{}", fl!("solve-cleared"));
+	} else {
+		println!("{}", fl!("solve-stuck", revealed = revealed, safe = safe));
+	}
+	Ok(())
+}
+
+fn replay(args: &BoardArgs, config: &Config, moves: &[String], json: bool) -> Result<(), Error> {
+	let mut field = args.field(config)?;
+	let seed = args.get_seed();
+
+	let mut played = 0;
+	for text in moves {
+		if moves::progress(&field) != Progress::Playing {
+			break;
+		}
+
+		let invalid = || Error::new(&fl!("error-move", value = text.as_str()));
+		let (coords, action) = text.rsplit_once(',').ok_or_else(invalid)?;
+		let coords = parse_coords(coords).map_err(|_| invalid())?;
+		let action = match action.trim() {
+			"r" => Move::Reveal,
+			"f" => Move::Flag,
+			"?" => Move::Unknown,
+			"c" => Move::Chord,
+			_ => return Err(invalid()),
+		};
+		moves::play(&mut field, action, &coords, seed)?;
+		played += 1;
+	}
+
+	if json {
+		let (seed, code) = seed_and_code(&field);
+		println!(
+			"{}",
+			json!({
+				"state": state(&field),
+				"moves": played,
+				"seed": seed,
+				"code": code,
+				"board": visible_board(&field),
+			})
+		);
+		return Ok(());
+	}
+
+	println!("{}", field);
+	println!(
+		"{}",
+		match state(&field) {
+			"lost" => fl!("tui-lost"),
+			"won" => fl!("tui-won"),
+			_ => fl!("replay-running", moves = played),
+		}
+	);
+	Ok(())
+}
+
+fn stats(json: bool) -> Result<(), Error> {
+	let stats = DailyStats::load();
+	let today = Date::today();
+
+	if json {
+		let results = stats
+			.results
+			.iter()
+			.map(|(date, won)| (date.to_string(), Value::from(*won)))
+			.collect::<Map<_, _>>();
+		println!(
+			"{}",
+			json!({
+				"played": stats.results.len(),
+				"won": stats.won(),
+				"streak": stats.streak(today),
+				"results": results,
+			})
+		);
+		return Ok(());
+	}
+
+	println!("{}", stats.summary(today));
+	Ok(())
+}
+
+// seeds count up from the given one, so runs with the same seed generate the same boards
+fn bench(args: &BoardArgs, config: &Config, games: u32, json: bool) -> Result<(), Error> {
+	let template = args.field(config)?;
+	if template.is_initialized() {
+		return Err(Error::new(&fl!("error-bench-board")));
+	}
+	let start = middle(&template);
+	let seed = args.get_seed();
+
+	let timer = Instant::now();
+	for i in 0..games {
+		let mut field = template.clone();
+		field.init(&start, seed.wrapping_add(i as u64))?;
+	}
+	let seconds = timer.elapsed().as_secs_f64();
+	let rate = games as f64 / seconds.max(f64::EPSILON);
+
+	if json {
+		println!(
+			"{}",
+			json!({
+				"games": games,
+				"seconds": seconds,
+				"per_second": rate,
+			})
+		);
+		return Ok(());
+	}
+
+	println!(
+		"{}",
+		fl!(
+			"bench-result",
+			games = games,
+			seconds = format!("{:.3}", seconds),
+			rate = format!("{:.0}", rate)
+		)
+	);
+	Ok(())
+}
+
+fn simulate(
+	args: &BoardArgs,
+	config: &Config,
+	games: u32,
+	presets: &[Difficulty],
+	threads: Option<usize>,
+	json: bool,
+) -> Result<(), Error> {
+	let threads = threads
+		.or_else(|| thread::available_parallelism().ok().map(|n| n.get()))
+		.unwrap_or(1);
+	let seed = args.get_seed();
+	let seeds: Vec<u64> = (0..games as u64).map(|i| seed.wrapping_add(i)).collect();
+
+	// no preset means the settings as they are
+	let presets: Vec<Option<Difficulty>> = if presets.is_empty() {
+		vec![None]
+	} else {
+		presets.iter().copied().map(Some).collect()
+	};
+
+	let mut reports = Vec::new();
+	for preset in presets {
+		let mut config = config.clone();
+		if let Some(difficulty) = preset {
+			config.difficulty = difficulty;
+		}
+		let template = args.field(&config)?;
+		if template.is_initialized() {
+			return Err(Error::new(&fl!("error-bench-board")));
+		}
+
+		let timer = Instant::now();
+		let results = simulation::simulate(&template, &seeds, threads)?;
+		let seconds = timer.elapsed().as_secs_f64();
+		let summary = Summary::new(&results);
+		let rate = summary.games as f64 / seconds.max(f64::EPSILON);
+		let name = preset
+			.and_then(|p| p.to_possible_value())
+			.map(|v| v.get_name().to_string());
+
+		if !json {
+			let limit = template.get_limit();
+			println!(
+				"{}",
+				fl!(
+					"simulate-result",
+					board = format!("{}×{}×{}", limit.x, limit.y, limit.z),
+					mines = template.get_num_mines(),
+					games = summary.games,
+					won = summary.won,
+					rate = format!("{:.1}", summary.win_rate * 100.0),
+					guesses = format!("{:.2}", summary.average_guesses)
+				)
+			);
+			println!(
+				"{}",
+				fl!(
+					"simulate-bbbv",
+					min = summary.bbbv_min(),
+					mean = format!("{:.1}", summary.bbbv_mean()),
+					max = summary.bbbv_max()
+				)
+			);
+			println!(
+				"{}",
+				fl!(
+					"simulate-speed",
+					seconds = format!("{:.3}", seconds),
+					rate = format!("{:.0}", rate),
+					threads = threads
+				)
+			);
+			continue;
+		}
+
+		let histogram = summary
+			.bbbv
+			.iter()
+			.map(|(bbbv, count)| (bbbv.to_string(), Value::from(*count)))
+			.collect::<Map<_, _>>();
+		let limit = template.get_limit();
+		reports.push(json!({
+			"preset": name,
+			"width": limit.x,
+			"height": limit.y,
+			"layers": limit.z,
+			"mines": template.get_num_mines(),
+			"games": summary.games,
+			"won": summary.won,
+			"win_rate": summary.win_rate,
+			"average_guesses": summary.average_guesses,
+			"bbbv": {
+				"min": summary.bbbv_min(),
+				"mean": summary.bbbv_mean(),
+				"max": summary.bbbv_max(),
+				"histogram": histogram,
+			},
+			"seconds": seconds,
+			"per_second": rate,
+		}));
+	}
+
+	if json {
+		println!(
+			"{}",
+			json!({
+				"seed": seed.to_string(),
+				"threads": threads,
+				"results": reports,
+			})
+		);
+	}
+	Ok(())
+}
+
+fn analyze_board(
+	args: &BoardArgs,
+	config: &Config,
+	start: Option<Coordintes>,
+	json: bool,
+) -> Result<(), Error> {
+	let field = started_board(args, config, start)?;
+	let analysis = analyze(&field);
+
+	if json {
+		let (seed, code) = seed_and_code(&field);
+		println!(
+			"{}",
+			json!({
+				"tiles": analysis.tiles,
+				"mines": analysis.mines,
+				"density": analysis.density,
+				"bbbv": analysis.bbbv,
+				"openings": analysis.openings,
+				"solvable": analysis.solvable,
+				"seed": seed,
+				"code": code,
+			})
+		);
+		return Ok(());
+	}
+
+	println!(
+		"{}",
+		fl!(
+			"analyze-board",
+			tiles = analysis.tiles,
+			mines = analysis.mines,
+			density = format!("{:.1}", analysis.density * 100.0)
+		)
+	);
+	println!(
+		"{}",
+		fl!(
+			"analyze-bbbv",
+			bbbv = analysis.bbbv,
+			openings = analysis.openings
+		)
+	);
+	if analysis.solvable {
+		println!("{}", fl!("editor-unique"));
+	} else {
+		println!("{}", fl!("editor-needs-guess"));
+	}
+	Ok(())
+}
@@ -0,0 +1,215 @@
+use std::{env, fs, path::PathBuf};
+
+use clap::ValueEnum;
+#[cfg(feature = "gui")]
+use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::field::{
+	rules::Rule,
+	topology::{Grid, Neighbourhood, Topology},
+	SafeStart,
+};
+
+pub const APP_ID: &str = "de.teddy-kun.Spacemines";
+pub const CONFIG_VERSION: u64 = 1;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum Difficulty {
+	#[default]
+	Beginner,
+	Intermediate,
+	Expert,
+}
+
+impl Difficulty {
+	#[cfg(feature = "gui")]
+	pub const ALL: [Difficulty; 3] = [
+		Difficulty::Beginner,
+		Difficulty::Intermediate,
+		Difficulty::Expert,
+	];
+
+	// returns width, height and number of mines of the preset
+	pub fn dimensions(&self) -> (u8, u8, u16) {
+		match self {
+			Difficulty::Beginner => (9, 9, 10),
+			Difficulty::Intermediate => (16, 16, 40),
+			Difficulty::Expert => (30, 16, 99),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+	#[default]
+	System,
+	Light,
+	Dark,
+}
+
+#[cfg(feature = "gui")]
+impl Theme {
+	pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+	pub fn theme(&self) -> cosmic::Theme {
+		match self {
+			Theme::System => cosmic::theme::system_preference(),
+			Theme::Light => cosmic::Theme::light(),
+			Theme::Dark => cosmic::Theme::dark(),
+		}
+	}
+}
+
+// look of the tiles in the GUI, every skin has a light and a dark variant following the theme
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Skin {
+	#[default]
+	Classic,
+	Flat,
+	HighContrast,
+	// flat tiles with a number palette that stays distinguishable with color vision deficiencies
+	Colorblind,
+}
+
+#[cfg(feature = "gui")]
+impl Skin {
+	pub const ALL: [Skin; 4] = [
+		Skin::Classic,
+		Skin::Flat,
+		Skin::HighContrast,
+		Skin::Colorblind,
+	];
+}
+
+/// Settings shared by both frontends.
+/// The GUI reads and writes them through cosmic-config, the TUI reads the same files directly.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "gui", derive(CosmicConfigEntry), version = 1)]
+pub struct Config {
+	pub difficulty: Difficulty,
+	pub question_marks: bool,
+	pub safe_start: SafeStart,
+	pub topology: Topology,
+	pub grid: Grid,
+	pub neighbourhood: Neighbourhood,
+	pub mines_per_tile: u8,
+	pub rule: Rule,
+	// mixed into the daily challenges, see Args
+	pub daily_salt: String,
+	// 1 for the classic flat field
+	pub layers: u8,
+	pub theme: Theme,
+	pub skin: Skin,
+	pub tile_size: u16,
+	pub confirm_prompts: bool,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Config {
+			difficulty: Difficulty::default(),
+			question_marks: true,
+			safe_start: SafeStart::default(),
+			topology: Topology::default(),
+			grid: Grid::default(),
+			neighbourhood: Neighbourhood::default(),
+			mines_per_tile: 1,
+			rule: Rule::default(),
+			daily_salt: String::new(),
+			layers: 1,
+			theme: Theme::default(),
+			skin: Skin::default(),
+			tile_size: 16,
+			confirm_prompts: true,
+		}
+	}
+}
+
+impl Config {
+	// cosmic-config stores every field in its own file containing the value as RON
+	// this reads them from the same location, so the TUI does not depend on libcosmic
+	pub fn load() -> Config {
+		let mut config = Config::default();
+		let dir = match config_dir() {
+			None => return config,
+			Some(d) => d,
+		};
+
+		if let Some(v) = read_entry(&dir, "difficulty") {
+			config.difficulty = v;
+		}
+		if let Some(v) = read_entry(&dir, "question_marks") {
+			config.question_marks = v;
+		}
+		if let Some(v) = read_entry(&dir, "safe_start") {
+			config.safe_start = v;
+		}
+		if let Some(v) = read_entry(&dir, "topology") {
+			config.topology = v;
+		}
+		if let Some(v) = read_entry(&dir, "grid") {
+			config.grid = v;
+		}
+		if let Some(v) = read_entry(&dir, "neighbourhood") {
+			config.neighbourhood = v;
+		}
+		if let Some(v) = read_entry(&dir, "mines_per_tile") {
+			config.mines_per_tile = v;
+		}
+		if let Some(v) = read_entry(&dir, "rule") {
+			config.rule = v;
+		}
+		if let Some(v) = read_entry(&dir, "daily_salt") {
+			config.daily_salt = v;
+		}
+		if let Some(v) = read_entry(&dir, "layers") {
+			config.layers = v;
+		}
+		if let Some(v) = read_entry(&dir, "theme") {
+			config.theme = v;
+		}
+		if let Some(v) = read_entry(&dir, "skin") {
+			config.skin = v;
+		}
+		if let Some(v) = read_entry(&dir, "tile_size") {
+			config.tile_size = v;
+		}
+		if let Some(v) = read_entry(&dir, "confirm_prompts") {
+			config.confirm_prompts = v;
+		}
+
+		config
+	}
+}
+
+// mirrors the path used by cosmic-config: $XDG_CONFIG_HOME/cosmic/<app id>/v<version>
+fn config_dir() -> Option<PathBuf> {
+	let base = match env::var_os("XDG_CONFIG_HOME") {
+		Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+		_ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+	};
+
+	Some(
+		base.join("cosmic")
+			.join(APP_ID)
+			.join(format!("v{}", CONFIG_VERSION)),
+	)
+}
+
+// $XDG_DATA_HOME/<app id>, for everything the player creates that is not a setting
+pub fn data_dir() -> Option<PathBuf> {
+	let base = match env::var_os("XDG_DATA_HOME") {
+		Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+		_ => PathBuf::from(env::var_os("HOME")?)
+			.join(".local")
+			.join("share"),
+	};
+
+	Some(base.join(APP_ID))
+}
+
+fn read_entry<T: DeserializeOwned>(dir: &std::path::Path, key: &str) -> Option<T> {
+	let text = fs::read_to_string(dir.join(key)).ok()?;
+	ron::from_str(&text).ok()
+}
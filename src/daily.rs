@@ -0,0 +1,330 @@
+use std::{collections::BTreeMap, fmt::Display, fs, path::PathBuf, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	args::seed_from_text,
+	config::{data_dir, Difficulty},
+	error::Error,
+	field::{
+		code::BoardCode, rules::Rule, tile::Coordintes, topology::Geometry, SafeStart,
+		PLACEMENT_VERSION,
+	},
+	fl,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Date {
+	pub year: i32,
+	pub month: u8,
+	pub day: u8,
+}
+
+impl Date {
+	// the date in the local timezone, so the challenge changes at midnight for everyone on the team
+	#[cfg(unix)]
+	pub fn today() -> Date {
+		// SAFETY: localtime_r only writes into the given struct, which is plain data
+		unsafe {
+			let now = libc::time(std::ptr::null_mut());
+			let mut tm: libc::tm = std::mem::zeroed();
+			if libc::localtime_r(&now, &mut tm).is_null() {
+				return Date::today_utc();
+			}
+			Date {
+				year: tm.tm_year + 1900,
+				month: tm.tm_mon as u8 + 1,
+				day: tm.tm_mday as u8,
+			}
+		}
+	}
+
+	#[cfg(not(unix))]
+	pub fn today() -> Date {
+		Date::today_utc()
+	}
+
+	fn today_utc() -> Date {
+		let seconds = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or_default();
+		Date::from_days((seconds / 86400) as i64)
+	}
+
+	/*
+	Days since 1970-01-01, counting both ways.
+	Uses the algorithm from http://howardhinnant.github.io/date_algorithms.html,
+	which treats years as starting in March so the leap day is at the end
+	*/
+	pub fn days(&self) -> i64 {
+		let year = self.year as i64 - (self.month <= 2) as i64;
+		let era = year.div_euclid(400);
+		let year_of_era = year - era * 400;
+		let month = (self.month as i64 + 9) % 12;
+		let day_of_year = (153 * month + 2) / 5 + self.day as i64 - 1;
+		let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+		era * 146097 + day_of_era - 719468
+	}
+
+	pub fn from_days(days: i64) -> Date {
+		let days = days + 719468;
+		let era = days.div_euclid(146097);
+		let day_of_era = days - era * 146097;
+		let year_of_era =
+			(day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+		let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+		let month = (5 * day_of_year + 2) / 153;
+		let day = day_of_year - (153 * month + 2) / 5 + 1;
+		let month = if month < 10 { month + 3 } else { month - 9 };
+		let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+		Date {
+			year: year as i32,
+			month: month as u8,
+			day: day as u8,
+		}
+	}
+
+	// 0 is sunday, 1970-01-01 was a thursday
+	pub fn weekday(&self) -> u8 {
+		(self.days() + 4).rem_euclid(7) as u8
+	}
+
+	fn days_in_month(year: i32, month: u8) -> u8 {
+		match month {
+			2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+			2 => 28,
+			4 | 6 | 9 | 11 => 30,
+			_ => 31,
+		}
+	}
+}
+
+impl Display for Date {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+	}
+}
+
+impl FromStr for Date {
+	type Err = Error;
+
+	// only accepts YYYY-MM-DD
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid = || Error::new(&fl!("error-date", value = s));
+
+		let parts: Vec<&str> = s.trim().split('-').collect();
+		if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+			return Err(invalid());
+		}
+
+		let date = Date {
+			year: parts[0].parse().map_err(|_| invalid())?,
+			month: parts[1].parse().map_err(|_| invalid())?,
+			day: parts[2].parse().map_err(|_| invalid())?,
+		};
+		if !(1..=12).contains(&date.month)
+			|| date.day == 0
+			|| date.day > Date::days_in_month(date.year, date.month)
+		{
+			return Err(invalid());
+		}
+
+		Ok(date)
+	}
+}
+
+/*
+The board of a day only depends on the date and the salt, so everyone using the same salt plays the same board.
+The settings of the player are ignored for this. The size rotates through the difficulties during the week,
+beginning the week easy and ending it hard, the first click is made in the middle
+*/
+pub fn board(date: Date, salt: &str) -> BoardCode {
+	let difficulty = match date.weekday() {
+		1 | 2 => Difficulty::Beginner,
+		3 | 4 => Difficulty::Intermediate,
+		_ => Difficulty::Expert,
+	};
+	let (width, height, mines) = difficulty.dimensions();
+
+	BoardCode {
+		seed: seed_from_text(&format!("daily {} {}", date, salt)),
+		placement: PLACEMENT_VERSION,
+		limit: Coordintes {
+			x: width,
+			y: height,
+			z: 1,
+		},
+		num_mines: mines,
+		mines_per_tile: 1,
+		start: Coordintes {
+			x: width / 2,
+			y: height / 2,
+			z: 0,
+		},
+		safe_start: SafeStart::Opening,
+		geometry: Geometry::default(),
+		rule: Rule::Classic,
+	}
+}
+
+// results of daily challenges, kept apart from everything else
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyStats {
+	// whether the challenge of the day was won, only the first game of a day counts
+	pub results: BTreeMap<Date, bool>,
+}
+
+impl DailyStats {
+	pub fn load() -> DailyStats {
+		stats_file()
+			.and_then(|path| fs::read_to_string(path).ok())
+			.and_then(|text| ron::from_str(&text).ok())
+			.unwrap_or_default()
+	}
+
+	pub fn save(&self) -> Result<(), Error> {
+		let path = match stats_file() {
+			None => return Err(Error::new(&fl!("error-stats-save"))),
+			Some(p) => p,
+		};
+		let text = ron::to_string(self).map_err(|e| Error::new(&e.to_string()))?;
+
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir).map_err(|e| Error::new(&e.to_string()))?;
+		}
+		fs::write(path, text).map_err(|e| Error::new(&e.to_string()))
+	}
+
+	// returns false if the day already has a result
+	pub fn record(&mut self, date: Date, won: bool) -> bool {
+		if self.results.contains_key(&date) {
+			return false;
+		}
+		self.results.insert(date, won);
+		true
+	}
+
+	pub fn won(&self) -> usize {
+		self.results.values().filter(|w| **w).count()
+	}
+
+	// days won in a row up to today, not having played today yet does not end the streak
+	pub fn streak(&self, today: Date) -> u32 {
+		let mut day = today.days();
+		if !self.results.contains_key(&today) {
+			day -= 1;
+		}
+
+		let mut streak = 0;
+		while self.results.get(&Date::from_days(day)) == Some(&true) {
+			streak += 1;
+			day -= 1;
+		}
+		streak
+	}
+
+	// one line summing up the stats, shown after every daily challenge
+	pub fn summary(&self, today: Date) -> String {
+		fl!(
+			"daily-stats",
+			played = self.results.len(),
+			won = self.won(),
+			streak = self.streak(today)
+		)
+	}
+}
+
+fn stats_file() -> Option<PathBuf> {
+	Some(data_dir()?.join("daily.ron"))
+}
+
+#[cfg(test)]
+mod daily_tests {
+	use super::*;
+
+	#[test]
+	fn test_dates() -> Result<(), Error> {
+		let cases = [
+			("1970-01-01", 0, 4),
+			("2000-02-29", 11016, 2),
+			("2024-03-01", 19783, 5),
+			("1969-12-31", -1, 3),
+		];
+		for (text, days, weekday) in cases {
+			let date: Date = text.parse()?;
+			if date.days() != days || date.weekday() != weekday || date.to_string() != text {
+				return Err(Error::new("date converted wrongly"));
+			}
+			if Date::from_days(days) != date {
+				return Err(Error::new("days converted back wrongly"));
+			}
+		}
+
+		for invalid in [
+			"2023-02-29",
+			"2024-13-01",
+			"2024-1-01",
+			"yesterday",
+			"2024-04-31",
+		] {
+			if invalid.parse::<Date>().is_ok() {
+				return Err(Error::new("invalid date accepted"));
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_daily_board() -> Result<(), Error> {
+		let date: Date = "2024-06-03".parse()?;
+		if board(date, "") != board(date, "") {
+			return Err(Error::new("daily board is not deterministic"));
+		}
+		if board(date, "").seed == board(date, "team").seed {
+			return Err(Error::new("salt does not change the board"));
+		}
+
+		let next = Date::from_days(date.days() + 1);
+		if board(date, "").seed == board(next, "").seed {
+			return Err(Error::new("days share a board"));
+		}
+
+		// a monday, which should be easy
+		if board(date, "").limit.x != 9 {
+			return Err(Error::new("wrong difficulty for the weekday"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_streak() -> Result<(), Error> {
+		let today: Date = "2024-06-10".parse()?;
+		let day = |offset: i64| Date::from_days(today.days() - offset);
+
+		let mut stats = DailyStats::default();
+		for offset in [1, 2, 3] {
+			stats.record(day(offset), true);
+		}
+		stats.record(day(4), false);
+		stats.record(day(5), true);
+		if stats.streak(today) != 3 {
+			return Err(Error::new("streak counted wrongly"));
+		}
+
+		// only the first game of a day counts
+		if stats.record(day(1), false) || stats.streak(today) != 3 {
+			return Err(Error::new("day recorded twice"));
+		}
+
+		stats.record(today, false);
+		if stats.streak(today) != 0 || stats.won() != 4 {
+			return Err(Error::new("lost day did not end the streak"));
+		}
+
+		Ok(())
+	}
+}
@@ -16,6 +16,11 @@ impl Error {
 		}
 	}
 
+	// the message without the backtrace, e.g. for showing it to the user
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+
 	// prints the error to stderr
 	pub fn out(&self) {
 		eprintln!("{}", self);
@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+
+use super::{solver, Field};
+
+// numbers describing a board, used to compare boards and how they are generated
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+	pub tiles: usize,
+	pub mines: u16,
+	pub density: f64,
+	// 3BV, the fewest clicks that clear the board without flags or chords
+	pub bbbv: usize,
+	// areas of empty tiles that open up with a single click
+	pub openings: usize,
+	// whether the solver can finish the board from the tiles revealed so far
+	pub solvable: bool,
+}
+
+/*
+Every opening takes one click, which also reveals the numbers around it.
+Every number not revealed by an opening takes a click of its own.
+Openings follow the rule of the field, numbers that may lie do not open anything
+*/
+pub fn analyze(field: &Field) -> Analysis {
+	let opens = |i: usize| !field.field[i].is_mine && field.rule.proves_empty(field.field[i].shown);
+	let mut opened = HashSet::new();
+	let mut reached = HashSet::new();
+	let mut openings = 0;
+	for index in 0..field.size() {
+		if !opens(index) || opened.contains(&index) {
+			continue;
+		}
+
+		openings += 1;
+		let mut stack = vec![index];
+		opened.insert(index);
+		while let Some(i) = stack.pop() {
+			reached.insert(i);
+			let coords = match field.index_to_coordintes(i) {
+				Err(_) => continue,
+				Ok(c) => c,
+			};
+			for n in field.opened_by(&coords) {
+				let ni = match field.get_index(&n) {
+					Err(_) => continue,
+					Ok(i) => i,
+				};
+				reached.insert(ni);
+				if opens(ni) && opened.insert(ni) {
+					stack.push(ni);
+				}
+			}
+		}
+	}
+
+	let clicks = (0..field.size())
+		.filter(|i| !field.field[*i].is_mine && !reached.contains(i))
+		.count();
+	let bbbv = openings + clicks;
+
+	let tiles = field.size();
+	Analysis {
+		tiles,
+		mines: field.get_num_mines(),
+		density: field.get_num_mines() as f64 / tiles.max(1) as f64,
+		bbbv,
+		openings,
+		solvable: solver::solvable_from_here(field),
+	}
+}
+
+#[cfg(test)]
+mod analysis_tests {
+	use super::*;
+	use crate::{error::Error, field::tile::Coordintes};
+
+	#[test]
+	fn test_bbbv() -> Result<(), Error> {
+		let limit = Coordintes { x: 3, y: 3, z: 1 };
+
+		// a mine in the corner leaves one opening that touches every number
+		let corner = Field::from_layout(limit, &[1, 0, 0, 0, 0, 0, 0, 0, 0])?;
+		let a = analyze(&corner);
+		if a.bbbv != 1 || a.openings != 1 || a.tiles != 9 {
+			return Err(Error::new("corner mine analyzed wrongly"));
+		}
+
+		// a mine in the middle leaves no opening, every number needs a click
+		let middle = Field::from_layout(limit, &[0, 0, 0, 0, 1, 0, 0, 0, 0])?;
+		let a = analyze(&middle);
+		if a.bbbv != 8 || a.openings != 0 || a.solvable {
+			return Err(Error::new("middle mine analyzed wrongly"));
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,287 @@
+use std::{fmt::Display, str::FromStr};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+use super::{
+	rules::Rule,
+	tile::Coordintes,
+	topology::{Geometry, Grid, Neighbourhood, Topology},
+	Field, SafeStart,
+};
+use crate::{error::Error, fl};
+
+// bump this when the layout of the bytes changes, older codes should keep working
+const FORMAT_VERSION: u8 = 1;
+
+/*
+Everything needed to generate the same field again, including the first click.
+Codes are the settings as bytes followed by a CRC-32 of them, written in url safe base64
+so they can be pasted anywhere without being mangled
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoardCode {
+	pub seed: u64,
+	pub placement: u8,
+	pub limit: Coordintes,
+	pub num_mines: u16,
+	pub mines_per_tile: u8,
+	pub start: Coordintes,
+	pub safe_start: SafeStart,
+	pub geometry: Geometry,
+	pub rule: Rule,
+}
+
+impl BoardCode {
+	// a field with the settings of the code, not initialized yet
+	pub fn field(&self) -> Field {
+		let mut field = Field::new_3d(self.limit.x, self.limit.y, self.limit.z, self.num_mines);
+		field.set_mines_per_tile(self.mines_per_tile);
+		field.set_safe_start(self.safe_start);
+		field.set_geometry(self.geometry.clone());
+		field.set_rule(self.rule);
+		field.set_placement(self.placement);
+		field
+	}
+
+	// the field with the first click already made
+	pub fn play(&self) -> Result<Field, Error> {
+		let mut field = self.field();
+		field.init(&self.start, self.seed)?;
+		field.reveal(&self.start)?;
+		Ok(field)
+	}
+
+	fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = vec![FORMAT_VERSION, self.placement];
+		bytes.extend(self.seed.to_le_bytes());
+		bytes.extend([self.limit.x, self.limit.y, self.limit.z]);
+		bytes.extend(self.num_mines.to_le_bytes());
+		bytes.push(self.mines_per_tile);
+		bytes.extend([self.start.x, self.start.y, self.start.z]);
+
+		// the numbers of the variants are part of the format, so they must not change when variants are added
+		bytes.push(match self.safe_start {
+			SafeStart::Tile => 0,
+			SafeStart::Opening => 1,
+			SafeStart::NoGuess => 2,
+			SafeStart::Relocate => 3,
+		});
+		bytes.push(match self.geometry.topology {
+			Topology::Rectangle => 0,
+			Topology::Cylinder => 1,
+			Topology::Torus => 2,
+		});
+		bytes.push(match self.geometry.grid {
+			Grid::Square => 0,
+			Grid::Hex => 1,
+		});
+		bytes.push(match self.rule {
+			Rule::Classic => 0,
+			Rule::Liar => 1,
+			Rule::Double => 2,
+			Rule::Negative => 3,
+			Rule::Orthogonal => 4,
+		});
+		match &self.geometry.neighbourhood {
+			Neighbourhood::Moore => bytes.push(0),
+			Neighbourhood::VonNeumann => bytes.push(1),
+			Neighbourhood::Knight => bytes.push(2),
+			Neighbourhood::Radius2 => bytes.push(3),
+			Neighbourhood::Custom(offsets) => {
+				bytes.extend([4, offsets.len() as u8]);
+				for (x, y) in offsets {
+					bytes.extend([*x as u8, *y as u8]);
+				}
+			}
+		}
+
+		bytes
+	}
+
+	fn from_bytes(bytes: &[u8]) -> Option<BoardCode> {
+		let mut bytes = bytes.iter().copied();
+		let mut next = || bytes.next();
+
+		if next()? != FORMAT_VERSION {
+			return None;
+		}
+		let placement = next()?;
+		let mut seed = [0; 8];
+		for b in &mut seed {
+			*b = next()?;
+		}
+		let limit = Coordintes {
+			x: next()?,
+			y: next()?,
+			z: next()?,
+		};
+		let num_mines = u16::from_le_bytes([next()?, next()?]);
+		let mines_per_tile = next()?;
+		let start = Coordintes {
+			x: next()?,
+			y: next()?,
+			z: next()?,
+		};
+
+		let safe_start = match next()? {
+			0 => SafeStart::Tile,
+			1 => SafeStart::Opening,
+			2 => SafeStart::NoGuess,
+			3 => SafeStart::Relocate,
+			_ => return None,
+		};
+		let topology = match next()? {
+			0 => Topology::Rectangle,
+			1 => Topology::Cylinder,
+			2 => Topology::Torus,
+			_ => return None,
+		};
+		let grid = match next()? {
+			0 => Grid::Square,
+			1 => Grid::Hex,
+			_ => return None,
+		};
+		let rule = match next()? {
+			0 => Rule::Classic,
+			1 => Rule::Liar,
+			2 => Rule::Double,
+			3 => Rule::Negative,
+			4 => Rule::Orthogonal,
+			_ => return None,
+		};
+		let neighbourhood = match next()? {
+			0 => Neighbourhood::Moore,
+			1 => Neighbourhood::VonNeumann,
+			2 => Neighbourhood::Knight,
+			3 => Neighbourhood::Radius2,
+			4 => {
+				let mut offsets = Vec::new();
+				for _ in 0..next()? {
+					offsets.push((next()? as i8, next()? as i8));
+				}
+				Neighbourhood::Custom(offsets)
+			}
+			_ => return None,
+		};
+
+		// trailing bytes mean the code was damaged or made by a newer version
+		if next().is_some() {
+			return None;
+		}
+
+		Some(BoardCode {
+			seed: u64::from_le_bytes(seed),
+			placement,
+			limit,
+			num_mines,
+			mines_per_tile,
+			start,
+			safe_start,
+			geometry: Geometry {
+				grid,
+				topology,
+				neighbourhood,
+			},
+			rule,
+		})
+	}
+}
+
+fn checksum(bytes: &[u8]) -> [u8; 4] {
+	crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC)
+		.checksum(bytes)
+		.to_le_bytes()
+}
+
+impl Display for BoardCode {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let mut bytes = self.to_bytes();
+		bytes.extend(checksum(&bytes));
+		write!(f, "{}", URL_SAFE_NO_PAD.encode(bytes))
+	}
+}
+
+impl FromStr for BoardCode {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid = || Error::new(&fl!("error-board-code"));
+
+		let bytes = URL_SAFE_NO_PAD.decode(s.trim()).map_err(|_| invalid())?;
+		if bytes.len() < 4 {
+			return Err(invalid());
+		}
+
+		let (data, sum) = bytes.split_at(bytes.len() - 4);
+		if checksum(data) != sum {
+			return Err(invalid());
+		}
+
+		BoardCode::from_bytes(data).ok_or_else(invalid)
+	}
+}
+
+#[cfg(test)]
+mod code_tests {
+	use super::*;
+
+	#[test]
+	fn test_round_trip() -> Result<(), Error> {
+		let mut f = Field::new_3d(12, 9, 2, 30);
+		f.set_mines_per_tile(2);
+		f.set_safe_start(SafeStart::Opening);
+		f.set_rule(Rule::Liar);
+		f.set_geometry(Geometry {
+			grid: Grid::Hex,
+			topology: Topology::Cylinder,
+			neighbourhood: Neighbourhood::Custom(vec![(1, 0), (-2, 1), (0, -1)]),
+		});
+		let start = Coordintes { x: 3, y: 7, z: 1 };
+		f.init(&start, 987654321)?;
+		f.reveal(&start)?;
+
+		let code = match f.board_code() {
+			None => return Err(Error::new("initialized field has no code")),
+			Some(c) => c,
+		};
+		let parsed: BoardCode = code.to_string().parse()?;
+		if parsed != code {
+			return Err(Error::new("code changed while being written and read"));
+		}
+
+		let played = parsed.play()?;
+		if played.get_field() != f.get_field() {
+			return Err(Error::new("code gives a different field"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_damaged_codes() -> Result<(), Error> {
+		let mut f = Field::new(9, 9, 10);
+		f.init(&Coordintes::new(), 1)?;
+		let code = match f.board_code() {
+			None => return Err(Error::new("initialized field has no code")),
+			Some(c) => c.to_string(),
+		};
+
+		// changing any single character has to be noticed
+		for i in 0..code.len() {
+			let mut damaged: Vec<char> = code.chars().collect();
+			damaged[i] = if damaged[i] == 'A' { 'B' } else { 'A' };
+			let damaged: String = damaged.into_iter().collect();
+			if damaged.parse::<BoardCode>().is_ok() {
+				return Err(Error::new("damaged code accepted"));
+			}
+		}
+
+		for garbage in ["", "not a code", "AAAA"] {
+			if garbage.parse::<BoardCode>().is_ok() {
+				return Err(Error::new("garbage accepted as code"));
+			}
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,345 @@
+use std::{fs, path::Path};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::{tile::Coordintes, Field};
+use crate::{error::Error, fl};
+
+// layouts of boards as used by other minesweeper programs and websites
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+	// one line per row, * for mines and . for empty tiles
+	Ascii,
+	// Minesweeper Board Format, the binary boards read by Arbiter and Viennasweeper
+	Mbf,
+	// {"width": 9, "height": 9, "mines": [[x, y], ...]}, see write_json
+	Json,
+}
+
+impl Format {
+	// guesses the format from the file extension, anything unknown is read as text
+	pub fn from_path(path: &Path) -> Format {
+		match path
+			.extension()
+			.and_then(|e| e.to_str())
+			.map(|e| e.to_lowercase())
+			.as_deref()
+		{
+			Some("mbf") => Format::Mbf,
+			Some("json") => Format::Json,
+			_ => Format::Ascii,
+		}
+	}
+
+	pub fn read(&self, data: &[u8]) -> Result<Field, Error> {
+		match self {
+			Format::Ascii => read_ascii(&String::from_utf8_lossy(data)),
+			Format::Mbf => read_mbf(data),
+			Format::Json => read_json(&String::from_utf8_lossy(data)),
+		}
+	}
+
+	pub fn write(&self, field: &Field) -> Result<Vec<u8>, Error> {
+		match self {
+			Format::Ascii => write_ascii(field).map(String::into_bytes),
+			Format::Mbf => write_mbf(field),
+			Format::Json => write_json(field).map(String::into_bytes),
+		}
+	}
+}
+
+// reads a board, the format is guessed from the extension if not given
+pub fn import(path: &Path, format: Option<Format>) -> Result<Field, Error> {
+	let data = fs::read(path).map_err(|e| Error::new(&e.to_string()))?;
+	format
+		.unwrap_or_else(|| Format::from_path(path))
+		.read(&data)
+}
+
+pub fn export(field: &Field, path: &Path, format: Option<Format>) -> Result<(), Error> {
+	let data = format
+		.unwrap_or_else(|| Format::from_path(path))
+		.write(field)?;
+	fs::write(path, data).map_err(|e| Error::new(&e.to_string()))
+}
+
+// text and Arbiter boards only know flat fields with one mine per tile
+fn check_classic(field: &Field) -> Result<(), Error> {
+	if field.limit.z > 1 || field.field.iter().any(|t| t.mines > 1) {
+		return Err(Error::new(&fl!("error-format-unsupported")));
+	}
+	Ok(())
+}
+
+// numbers are accepted as empty tiles, so boards copied from a solved game can be read as well
+fn read_ascii(text: &str) -> Result<Field, Error> {
+	let rows: Vec<&str> = text
+		.lines()
+		.map(str::trim)
+		.filter(|l| !l.is_empty())
+		.collect();
+	let width = rows.first().map(|r| r.chars().count()).unwrap_or_default();
+	if width > u8::MAX as usize || rows.len() > u8::MAX as usize {
+		return Err(Error::new(&fl!("error-layout-size")));
+	}
+
+	let mut mines = Vec::new();
+	for row in &rows {
+		if row.chars().count() != width {
+			return Err(Error::new(&fl!("error-layout-size")));
+		}
+		for c in row.chars() {
+			mines.push(match c {
+				'*' => 1,
+				'.' | '0'..='8' => 0,
+				_ => {
+					return Err(Error::new(&fl!(
+						"error-layout-character",
+						character = c.to_string()
+					)))
+				}
+			});
+		}
+	}
+
+	let limit = Coordintes {
+		x: width as u8,
+		y: rows.len() as u8,
+		z: 1,
+	};
+	Field::from_layout(limit, &mines)
+}
+
+fn write_ascii(field: &Field) -> Result<String, Error> {
+	check_classic(field)?;
+
+	let mut text = String::new();
+	for row in field.field.chunks(field.limit.x.max(1) as usize) {
+		for tile in row {
+			text.push(if tile.is_mine { '*' } else { '.' });
+		}
+		text.push('\n');
+	}
+	Ok(text)
+}
+
+// width, height, the number of mines as big endian u16, then x and y of every mine
+fn read_mbf(data: &[u8]) -> Result<Field, Error> {
+	let invalid = || Error::new(&fl!("error-layout-size"));
+	if data.len() < 4 {
+		return Err(invalid());
+	}
+
+	let limit = Coordintes {
+		x: data[0],
+		y: data[1],
+		z: 1,
+	};
+	let count = u16::from_be_bytes([data[2], data[3]]) as usize;
+	let positions = &data[4..];
+	if positions.len() < count * 2 {
+		return Err(invalid());
+	}
+
+	let mut mines = vec![0; limit.x as usize * limit.y as usize];
+	for pair in positions.chunks(2).take(count) {
+		let coords = Coordintes {
+			x: pair[0],
+			y: pair[1],
+			z: 0,
+		};
+		if !coords.is_inside(&limit) {
+			return Err(invalid());
+		}
+
+		// tiles only hold one mine in mbf, a repeated position would leave fewer mines than the header says
+		let i = coords.x as usize + coords.y as usize * limit.x as usize;
+		if mines[i] != 0 {
+			return Err(Error::new(&fl!(
+				"error-mbf-duplicate",
+				row = (coords.y as u16 + 1),
+				column = (coords.x as u16 + 1)
+			)));
+		}
+		mines[i] = 1;
+	}
+
+	Field::from_layout(limit, &mines)
+}
+
+fn write_mbf(field: &Field) -> Result<Vec<u8>, Error> {
+	check_classic(field)?;
+
+	let mut data = vec![field.limit.x, field.limit.y];
+	let mut positions = Vec::new();
+	for (i, tile) in field.field.iter().enumerate() {
+		if tile.is_mine {
+			let c = field.index_to_coordintes(i)?;
+			positions.extend([c.x, c.y]);
+		}
+	}
+	data.extend(((positions.len() / 2) as u16).to_be_bytes());
+	data.extend(positions);
+	Ok(data)
+}
+
+/*
+Every mine is listed as [x, y], or [x, y, z] on fields with layers.
+A tile listed more than once holds that many mines, layers default to 1
+*/
+#[derive(Serialize, Deserialize)]
+struct JsonLayout {
+	width: u8,
+	height: u8,
+	#[serde(default = "one_layer")]
+	layers: u8,
+	mines: Vec<Vec<u8>>,
+}
+
+fn one_layer() -> u8 {
+	1
+}
+
+fn write_json(field: &Field) -> Result<String, Error> {
+	let mut mines = Vec::new();
+	for (i, tile) in field.field.iter().enumerate() {
+		let c = match field.index_to_coordintes(i) {
+			Err(_) => continue,
+			Ok(c) => c,
+		};
+		for _ in 0..tile.mines {
+			if field.limit.z > 1 {
+				mines.push(vec![c.x, c.y, c.z]);
+			} else {
+				mines.push(vec![c.x, c.y]);
+			}
+		}
+	}
+
+	let layout = JsonLayout {
+		width: field.limit.x,
+		height: field.limit.y,
+		layers: field.limit.z,
+		mines,
+	};
+	serde_json::to_string(&layout)
+		.map(|text| text + "\n")
+		.map_err(|e| Error::new(&e.to_string()))
+}
+
+fn read_json(text: &str) -> Result<Field, Error> {
+	let invalid = || Error::new(&fl!("error-layout-json"));
+
+	let layout: JsonLayout = serde_json::from_str(text).map_err(|_| invalid())?;
+	let limit = Coordintes {
+		x: layout.width,
+		y: layout.height,
+		z: layout.layers,
+	};
+
+	let mut mines = vec![0u8; limit.x as usize * limit.y as usize * limit.z as usize];
+	for mine in &layout.mines {
+		let coords = match mine[..] {
+			[x, y] => Coordintes { x, y, z: 0 },
+			[x, y, z] => Coordintes { x, y, z },
+			_ => return Err(invalid()),
+		};
+		if !coords.is_inside(&limit) {
+			return Err(invalid());
+		}
+
+		let i = coords.x as usize
+			+ coords.y as usize * limit.x as usize
+			+ coords.z as usize * limit.x as usize * limit.y as usize;
+		mines[i] = mines[i].checked_add(1).ok_or_else(invalid)?;
+	}
+
+	Field::from_layout(limit, &mines)
+}
+
+#[cfg(test)]
+mod formats_tests {
+	use super::*;
+
+	fn random_field() -> Result<Field, Error> {
+		let mut f = Field::new(12, 7, 20);
+		f.init(&Coordintes::new(), 3)?;
+		Ok(f)
+	}
+
+	#[test]
+	fn test_round_trips() -> Result<(), Error> {
+		let f = random_field()?;
+		for format in [Format::Ascii, Format::Mbf, Format::Json] {
+			let imported = format.read(&format.write(&f)?)?;
+			if imported.layout() != f.layout() || imported.get_limit() != f.get_limit() {
+				return Err(Error::new("layout changed while being written and read"));
+			}
+		}
+
+		// json also keeps layers and tiles with multiple mines
+		let mut layout = vec![0; 3 * 2 * 2];
+		layout[0] = 2;
+		layout[7] = 1;
+		let limit = Coordintes { x: 3, y: 2, z: 2 };
+		let f = Field::from_layout(limit, &layout)?;
+		if Format::Json.read(&Format::Json.write(&f)?)?.layout() != layout {
+			return Err(Error::new("json lost layers or mine counts"));
+		}
+		if Format::Ascii.write(&f).is_ok() || Format::Mbf.write(&f).is_ok() {
+			return Err(Error::new("classic format wrote a field it cannot hold"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_from_layout() -> Result<(), Error> {
+		let f = Format::Ascii.read(b"*..\n...\n..*\n")?;
+		if !f.is_initialized() || f.get_num_mines() != 2 || f.board_code().is_some() {
+			return Err(Error::new("layout not taken as it is"));
+		}
+
+		let values: Vec<u8> = f.get_field().iter().map(|t| t.value).collect();
+		if values != [0, 1, 0, 1, 2, 1, 0, 1, 0] {
+			return Err(Error::new("values do not match the layout"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_invalid_input() -> Result<(), Error> {
+		let texts: [&[u8]; 3] = [b"*..\n..\n", b"*.x\n", b""];
+		for text in texts {
+			if Format::Ascii.read(text).is_ok() {
+				return Err(Error::new("invalid text board accepted"));
+			}
+		}
+
+		let jsons = [
+			"{\"width\": 3}",
+			"{\"width\": 3, \"height\": 3, \"mines\": [[3, 0]]}",
+			"{\"width\": 3, \"height\": 3, \"mines\": [[1, 1]]} trailing",
+			"[1, 2",
+		];
+		for json in jsons {
+			if Format::Json.read(json.as_bytes()).is_ok() {
+				return Err(Error::new("invalid json accepted"));
+			}
+		}
+
+		// a mine at 1, 2 on a board only 2 high
+		if Format::Mbf.read(&[2, 2, 0, 1, 1, 2]).is_ok() {
+			return Err(Error::new("invalid mbf accepted"));
+		}
+
+		// two mines declared, but both at 0, 1
+		if Format::Mbf.read(&[2, 2, 0, 2, 0, 1, 0, 1]).is_ok() {
+			return Err(Error::new("mbf with a repeated mine accepted"));
+		}
+
+		Ok(())
+	}
+}
@@ -2,75 +2,254 @@
 
 use std::{collections::HashSet, fmt::Display};
 
+pub mod analysis;
+pub mod code;
+pub mod formats;
+pub mod moves;
+pub mod puzzle;
+pub mod random;
+pub mod rules;
+pub mod simulation;
+pub mod solver;
 pub mod tile;
-use rand::{rngs::StdRng, SeedableRng};
-
+pub mod topology;
+pub mod versus;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use self::code::BoardCode;
+use self::random::FieldRng;
+use self::rules::Rule;
 use self::tile::{Coordintes, Tile};
+use self::topology::{Geometry, Grid, Neighbourhood, Topology};
 
 use super::error::Error;
+use crate::fl;
+
+// decides which tiles are kept free of mines around the first click
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum SafeStart {
+	// only the clicked tile is guaranteed to not be a mine
+	#[default]
+	Tile,
+	// the clicked tile and all of its neighbours are free, so the first click always opens an area
+	Opening,
+	// like Opening, but the whole field can be solved from there without guessing
+	NoGuess,
+	// mines are placed before the first click, a mine hit by it is moved away like on classic Windows
+	Relocate,
+}
+
+// version of the algorithm distributing the mines, a seed only gives the same field with the same version
+// bump this whenever place_mines changes and keep the older versions working
+pub const PLACEMENT_VERSION: u8 = 1;
 
-#[derive(Debug)]
+// fields failing to be solvable this often are played anyway, so starting a game always finishes
+const NO_GUESS_ATTEMPTS: usize = 1000;
+
+#[derive(Debug, Clone)]
 pub struct Field {
 	field: Vec<Tile>,
 	limit: Coordintes,
 	num_mines: u16,
+	// how many mines a single tile can hold, 1 in the classic game
+	mines_per_tile: u8,
 	has_init: bool,
+	// the layout was given instead of generated from the seed, see from_layout
+	handmade: bool,
+	// set once a mine was revealed
+	lost: bool,
 	seed: u64,
+	// first click, the field was generated around it
+	start: Coordintes,
+	placement: u8,
+	safe_start: SafeStart,
+	// the tiles around the first click were kept free of mines, see place_mines
+	opening: bool,
+	geometry: Geometry,
+	rule: Rule,
 }
 
 impl Field {
 	pub fn new(x: u8, y: u8, num_mines: u16) -> Field {
-		let size = x as u16 * y as u16;
-		let mines = if num_mines > size { size } else { num_mines };
+		Field::new_3d(x, y, 1, num_mines)
+	}
+
+	// a field made out of z layers stacked on top of each other
+	pub fn new_3d(x: u8, y: u8, z: u8, num_mines: u16) -> Field {
+		let size = x as usize * y as usize * z as usize;
 
 		Field {
-			field: vec![Tile::new(); size as usize],
-			limit: Coordintes { x, y },
-			num_mines: mines,
+			field: vec![Tile::new(); size],
+			limit: Coordintes { x, y, z },
+			// can be more than fit, see get_num_mines
+			num_mines,
+			mines_per_tile: 1,
 			has_init: false,
+			handmade: false,
+			lost: false,
 			seed: 0,
+			start: Coordintes::new(),
+			placement: PLACEMENT_VERSION,
+			safe_start: SafeStart::default(),
+			opening: false,
+			geometry: Geometry::default(),
+			rule: Rule::default(),
 		}
 	}
 
+	/*
+	A field with the given number of mines on every tile, in the same order as get_index.
+	It counts as initialized, so the first click does not move any mines and the layout is played exactly as given
+	*/
+	pub fn from_layout(limit: Coordintes, mines: &[u8]) -> Result<Field, Error> {
+		let mut field = Field::new_3d(limit.x, limit.y, limit.z, 0);
+		if mines.len() != field.size() || mines.is_empty() {
+			return Err(Error::new(&fl!("error-layout-size")));
+		}
+
+		for (tile, m) in field.field.iter_mut().zip(mines) {
+			tile.set_mines(*m);
+		}
+		let total: usize = mines.iter().map(|m| *m as usize).sum();
+		field.num_mines = total.min(u16::MAX as usize) as u16;
+		field.mines_per_tile = mines.iter().copied().max().unwrap_or_default().max(1);
+		field.check()?;
+		field.count_values(&mut FieldRng::new(0))?;
+		field.has_init = true;
+		field.handmade = true;
+
+		Ok(field)
+	}
+
+	// mines of every tile in the order of get_index, what from_layout takes
+	pub fn layout(&self) -> Vec<u8> {
+		self.field.iter().map(|t| t.mines).collect()
+	}
+
 	pub fn is_initialized(&self) -> bool {
 		self.has_init
 	}
 
+	pub fn has_lost(&self) -> bool {
+		self.lost
+	}
+
+	// the number of mines is capped to what fits into the field
 	pub fn get_num_mines(&self) -> u16 {
-		self.num_mines
+		let capacity = self.field.len() * self.mines_per_tile as usize;
+		self.num_mines.min(capacity.min(u16::MAX as usize) as u16)
+	}
+
+	pub fn get_mines_per_tile(&self) -> u8 {
+		self.mines_per_tile
+	}
+
+	// only has an effect before the field is initialized
+	pub fn set_mines_per_tile(&mut self, mines_per_tile: u8) {
+		self.mines_per_tile = mines_per_tile.max(1);
 	}
 
 	pub fn get_limit(&self) -> Coordintes {
 		self.limit
 	}
 
+	pub fn get_safe_start(&self) -> SafeStart {
+		self.safe_start
+	}
+
+	// only has an effect before the field is initialized
+	pub fn set_safe_start(&mut self, safe_start: SafeStart) {
+		self.safe_start = safe_start;
+	}
+
+	// code to share the field with, only exists once the first click decided the layout
+	// handmade layouts cannot be made from a seed, they have to be exported instead
+	pub fn board_code(&self) -> Option<BoardCode> {
+		if !self.has_init || self.handmade {
+			return None;
+		}
+
+		Some(BoardCode {
+			seed: self.seed,
+			placement: self.placement,
+			limit: self.limit,
+			num_mines: self.num_mines,
+			mines_per_tile: self.mines_per_tile,
+			start: self.start,
+			safe_start: self.safe_start,
+			geometry: self.geometry.clone(),
+			rule: self.rule,
+		})
+	}
+
+	pub fn get_placement(&self) -> u8 {
+		self.placement
+	}
+
+	// only has an effect before the field is initialized
+	pub fn set_placement(&mut self, placement: u8) {
+		self.placement = placement;
+	}
+
+	pub fn get_geometry(&self) -> &Geometry {
+		&self.geometry
+	}
+
+	// only has an effect before the field is initialized
+	pub fn set_geometry(&mut self, geometry: Geometry) {
+		self.geometry = geometry;
+	}
+
+	pub fn get_rule(&self) -> Rule {
+		self.rule
+	}
+
+	// only has an effect before the field is initialized
+	pub fn set_rule(&mut self, rule: Rule) {
+		self.rule = rule;
+	}
+
+	// all tiles next to the given one, depends on the grid and topology of the field
+	pub fn neighbours(&self, coords: &Coordintes) -> Vec<Coordintes> {
+		self.geometry.neighbours(coords, &self.limit)
+	}
+
 	pub fn get_field(&self) -> Vec<Tile> {
 		self.field.clone()
 	}
 
+	pub fn get_tile(&self, coords: &Coordintes) -> Result<&Tile, Error> {
+		let index = self.get_index(coords)?;
+		Ok(&self.field[index])
+	}
+
 	// The field internally is saved as a simple array. This function returns the index in the array where something at the given coordinates lives
-	// (0,0) is in the top left corner
+	// (0,0) is in the top left corner, layers follow each other
 	fn get_index(&self, coords: &Coordintes) -> Result<usize, Error> {
 		if !coords.is_inside(&self.limit) {
-			return Err(Error::new(
-				(String::from("requested coordinates ")
-					+ coords.to_string().as_str()
-					+ " are outside the grid")
-					.as_str(),
-			));
+			return Err(Error::new(&fl!(
+				"error-outside-grid",
+				coords = coords.to_string()
+			)));
 		}
 
-		Ok(coords.x as usize + (coords.y as usize * self.limit.x as usize))
+		let layer = self.limit.x as usize * self.limit.y as usize;
+		Ok(coords.x as usize
+			+ (coords.y as usize * self.limit.x as usize)
+			+ (coords.z as usize * layer))
 	}
 
-	fn index_to_coordintes(&self, index: usize) -> Result<(u8, u8), Error> {
+	fn index_to_coordintes(&self, index: usize) -> Result<Coordintes, Error> {
 		if index >= self.size() {
-			return Err(Error::new("index outside field"));
+			return Err(Error::new(&fl!("error-index-outside")));
 		}
 
+		let layer = self.limit.x as usize * self.limit.y as usize;
 		let x = (index % self.limit.x as usize) as u8;
-		let y = (index / self.limit.x as usize) as u8;
-		Ok((x, y))
+		let y = (index % layer / self.limit.x as usize) as u8;
+		let z = (index / layer) as u8;
+		Ok(Coordintes { x, y, z })
 	}
 
 	pub fn is_mine(&self, coords: &Coordintes) -> Result<bool, Error> {
@@ -88,18 +267,18 @@ impl Field {
 		Ok(self.field[index].unknown)
 	}
 
+	// cycles through the flag counts, then a question mark and back to nothing
 	pub fn toggle_mark(&mut self, coords: &Coordintes) -> Result<(), Error> {
 		let index = self.get_index(coords)?;
+		let tile = &mut self.field[index];
 
-		if self.field[index].flag {
-			self.field[index].flag = false;
-			self.field[index].unknown = true;
-		} else if self.field[index].unknown {
-			self.field[index].flag = false;
-			self.field[index].unknown = false;
+		if tile.unknown {
+			tile.unknown = false;
+		} else if tile.flags >= self.mines_per_tile {
+			tile.set_flags(0);
+			tile.unknown = true;
 		} else {
-			self.field[index].flag = true;
-			self.field[index].unknown = false;
+			tile.set_flags(tile.flags + 1);
 		}
 
 		Ok(())
@@ -113,8 +292,14 @@ impl Field {
 	pub fn flag(&mut self, coords: &Coordintes) -> Result<(), Error> {
 		let index = self.get_index(coords)?;
 
-		self.field[index].unknown = false;
-		self.field[index].flag = !self.field[index].flag;
+		// with multiple mines per tile this cycles through the flag counts
+		let tile = &mut self.field[index];
+		tile.unknown = false;
+		let flags = tile
+			.flags
+			.checked_add(1)
+			.filter(|f| *f <= self.mines_per_tile);
+		tile.set_flags(flags.unwrap_or(0));
 		Ok(())
 	}
 
@@ -122,24 +307,33 @@ impl Field {
 		let index = self.get_index(coords)?;
 
 		self.field[index].unknown = !self.field[index].unknown;
-		self.field[index].flag = false;
+		self.field[index].set_flags(0);
 		Ok(())
 	}
 
+	// tiles revealed together with the given one, the shown number has to prove them safe so the cascade cannot give away more
+	fn opened_by(&self, coords: &Coordintes) -> Vec<Coordintes> {
+		match self.get_tile(coords) {
+			Ok(t) if !t.is_mine && self.rule.proves_empty(t.shown) => {
+				self.rule.counted(&self.geometry, coords, &self.limit)
+			}
+			_ => Vec::new(),
+		}
+	}
+
 	fn recurse_reveal(&mut self, coords: &Coordintes) {
-		let mut to_reveal = coords.get_surrounding(&self.limit);
+		let mut to_reveal = self.opened_by(coords);
 
 		while let Some(working) = to_reveal.pop() {
 			if let Ok(index) = self.get_index(&working) {
-				let f = &mut self.field[index];
-				if f.value == 0 && !f.revealed {
-					let mut sur = working.get_surrounding(&self.limit);
+				if !self.field[index].revealed {
+					let mut sur = self.opened_by(&working);
 					to_reveal.append(&mut sur);
 					let mut dedupe = HashSet::new();
 					to_reveal.retain(|item| dedupe.insert(*item));
 				}
 
-				f.revealed = true;
+				self.field[index].revealed = true;
 			}
 		}
 	}
@@ -147,96 +341,309 @@ impl Field {
 	pub fn reveal(&mut self, coords: &Coordintes) -> Result<&Tile, Error> {
 		let index = self.get_index(coords)?;
 
-		if self.field[index].is_mine || self.field[index].revealed {
+		if self.field[index].is_mine {
+			self.lost = true;
 			return Ok(&self.field[index]);
 		}
 
-		// reveal all alround if we are 0
-		// ignore errors since we only error if we are outside, in which case we don't need to do anything
-		if self.field[index].value == 0 && !self.field[index].revealed {
-			self.recurse_reveal(coords);
+		if self.field[index].revealed {
+			return Ok(&self.field[index]);
 		}
 
+		// reveal all alround if we are 0, or whatever else the rule proves to be safe
+		self.recurse_reveal(coords);
+
 		self.field[index].revealed = true;
 
+		// the player knows the tiles kept free around the first click are safe, even where the numbers do not prove it
+		if self.opening && *coords == self.start {
+			for n in self.neighbours(coords) {
+				self.reveal(&n)?;
+			}
+		}
+
 		Ok(&self.field[index])
 	}
 
+	// reveals a tile in a versus game, a mine is found by the player instead of ending the game
+	// returns whether the tile held a mine
+	pub fn claim(&mut self, coords: &Coordintes, player: u8) -> Result<bool, Error> {
+		let index = self.get_index(coords)?;
+		if !self.field[index].is_mine {
+			self.reveal(coords)?;
+			return Ok(false);
+		}
+
+		let tile = &mut self.field[index];
+		tile.revealed = true;
+		tile.owner = Some(player);
+		tile.unknown = false;
+		tile.set_flags(0);
+		Ok(true)
+	}
+
+	// reveals every unflagged neighbour of an already revealed number, if enough flags are placed around it
+	// returns true if one of the revealed neighbours was a mine
+	pub fn chord(&mut self, coords: &Coordintes) -> Result<bool, Error> {
+		let index = self.get_index(coords)?;
+		let tile = &self.field[index];
+		if !tile.revealed || tile.is_mine {
+			return Ok(false);
+		}
+
+		if !self.rule.allows_chord() {
+			return Ok(false);
+		}
+
+		let surrounding = self.rule.counted(&self.geometry, coords, &self.limit);
+		let mut flags = 0;
+		for c in &surrounding {
+			flags += self.rule.weight(self.get_tile(c)?.flags, false);
+		}
+
+		if flags != self.field[index].shown {
+			return Ok(false);
+		}
+
+		let mut hit_mine = false;
+		for c in &surrounding {
+			if !self.is_flag(c)? && self.reveal(c)?.is_mine {
+				hit_mine = true;
+			}
+		}
+
+		Ok(hit_mine)
+	}
+
 	pub fn get_value(&self, coords: Coordintes) -> Result<u8, Error> {
 		let index = self.get_index(&coords)?;
 
 		Ok(self.field[index].value)
 	}
 
+	/*
+	Checks that the settings make a field that can be played.
+	The real number of mines around a tile is kept as u8 and the shown one as i16, so a tile whose neighbours are all full
+	of mines has to fit into them, with the largest weight of the rule and the one a liar adds
+	*/
+	pub fn check(&self) -> Result<(), Error> {
+		self.geometry.check(&self.limit)?;
+
+		let per_tile = self.mines_per_tile as usize;
+		let around = per_tile * self.geometry.max_neighbours(&self.limit);
+		let counted = per_tile * self.rule.max_counted(&self.geometry, &self.limit);
+		let shown = counted * self.rule.max_weight() as usize + 1;
+		if around > u8::MAX as usize || shown > i16::MAX as usize {
+			return Err(Error::new(&fl!(
+				"error-numbers-too-large",
+				mines = around.max(counted)
+			)));
+		}
+		Ok(())
+	}
+
 	/*
 	In minesweeper, the first field clicked should never be a mine, as such we only populate the field with mines,
 	after the player clicked on the first tile
 	*/
 	pub fn init(&mut self, player_start: &Coordintes, seed: u64) -> Result<(), Error> {
 		if !player_start.is_inside(&self.limit) {
-			return Err(Error::new("requested coordinates are outside the grid"));
+			return Err(Error::new(&fl!(
+				"error-outside-grid",
+				coords = player_start.to_string()
+			)));
 		}
 
 		if self.has_init {
-			return Err(Error::new("field already initialized"));
+			return Err(Error::new(&fl!("error-already-initialized")));
+		}
+
+		if self.placement != PLACEMENT_VERSION {
+			return Err(Error::new(&fl!(
+				"error-placement",
+				version = self.placement,
+				current = PLACEMENT_VERSION
+			)));
 		}
+		self.check()?;
 
 		self.seed = seed;
+		self.start = *player_start;
 
-		if self.num_mines as usize == self.field.len() {
+		let num_mines = self.get_num_mines();
+		if num_mines as usize == self.field.len() * self.mines_per_tile as usize {
 			for field in &mut self.field {
-				field.is_mine = true;
+				field.set_mines(self.mines_per_tile);
 			}
 			return Ok(());
 		}
 
-		let mut mines = 0;
-		let mut rng = StdRng::seed_from_u64(seed);
-		while mines < self.num_mines {
-			let coords = Coordintes::new_random(&self.limit, &mut rng);
-			if coords.x == player_start.x && coords.y == player_start.y {
-				continue;
+		let mut rng = FieldRng::new(seed);
+		let attempts = match self.safe_start {
+			SafeStart::NoGuess => NO_GUESS_ATTEMPTS,
+			_ => 1,
+		};
+		for _ in 0..attempts {
+			for tile in &mut self.field {
+				tile.set_mines(0);
+				tile.negative = false;
 			}
+			self.place_mines(player_start, &mut rng)?;
+			self.count_values(&mut rng)?;
 
-			let i = self.get_index(&coords)?;
-			if !self.field[i].is_mine {
-				mines += 1;
-				self.field[i].is_mine = true;
-			} else {
-				continue;
+			if self.safe_start != SafeStart::NoGuess || solver::solvable(self, player_start) {
+				break;
 			}
+		}
 
-			let mine_surroundings = coords.get_surrounding(&self.limit);
+		self.has_init = true;
 
-			for coords in mine_surroundings {
-				let i = self.get_index(&coords)?;
-				self.field[i].value += 1;
+		Ok(())
+	}
+
+	fn place_mines(&mut self, player_start: &Coordintes, rng: &mut FieldRng) -> Result<(), Error> {
+		let per_tile = self.mines_per_tile as usize;
+		let num_mines = self.get_num_mines();
+
+		// the clicked tile cannot stay free if the mines only fit with it, so it takes as few as possible
+		let free = (self.field.len() - 1) * per_tile;
+		let start_mines = (num_mines as usize).saturating_sub(free) as u8;
+		let start = self.get_index(player_start)?;
+
+		let mut safe = Vec::new();
+		self.opening = false;
+		let mut mines = 0;
+		if self.safe_start != SafeStart::Relocate {
+			self.field[start].set_mines(start_mines);
+			mines = start_mines as u16;
+			safe.push(*player_start);
+		}
+		if matches!(self.safe_start, SafeStart::Opening | SafeStart::NoGuess) {
+			let mut sur = self.neighbours(player_start);
+			// fall back to only keeping the clicked tile free if the other tiles cannot hold all mines
+			if num_mines as usize <= (self.field.len() - 1 - sur.len()) * per_tile {
+				safe.append(&mut sur);
+				self.opening = true;
 			}
 		}
 
-		self.has_init = true;
+		/*
+		Every free tile has one slot per mine it can hold, the mines go into a random selection of them.
+		Shuffling only as many slots as there are mines left is enough for the selection to be uniform,
+		and unlike picking random tiles until enough are free it never has to retry
+		*/
+		let mut excluded = Vec::new();
+		for c in &safe {
+			excluded.push(self.get_index(c)?);
+		}
+		let mut slots: Vec<usize> = (0..self.field.len())
+			.filter(|i| !excluded.contains(i))
+			.flat_map(|i| std::iter::repeat_n(i, per_tile - self.field[i].mines as usize))
+			.collect();
+		let missing = (num_mines - mines) as usize;
+		if missing > slots.len() {
+			return Err(Error::new(&fl!(
+				"error-mines-fit",
+				mines = num_mines,
+				placed = (mines as usize + slots.len())
+			)));
+		}
+		for i in 0..missing {
+			let j = rng.range(i..slots.len());
+			slots.swap(i, j);
+
+			let index = slots[i];
+			let more = self.field[index].mines + 1;
+			self.field[index].set_mines(more);
+		}
+
+		// mines under the first click go to the top left most tile with room left
+		while self.safe_start == SafeStart::Relocate && self.field[start].mines > start_mines {
+			let target = match (0..self.field.len())
+				.find(|i| *i != start && self.field[*i].mines < self.mines_per_tile)
+			{
+				None => break,
+				Some(i) => i,
+			};
+			let more = self.field[target].mines + 1;
+			self.field[target].set_mines(more);
+			let less = self.field[start].mines - 1;
+			self.field[start].set_mines(less);
+		}
+
+		// random numbers are only drawn for rules needing them, so classic fields stay the same for every seed
+		if self.rule == Rule::Negative {
+			for tile in &mut self.field {
+				tile.negative = tile.is_mine && rng.ratio(1, 3);
+			}
+		}
 
 		Ok(())
 	}
 
+	/*
+	Every tile counts the mines in its own neighbourhood. Counting from the mines outwards would only work
+	if being neighbours is symmetric, which custom neighbourhoods like "1,0" are not.
+	The value is the real number of mines around, the rule decides which number is shown to the player
+	*/
+	fn count_values(&mut self, rng: &mut FieldRng) -> Result<(), Error> {
+		// check makes sure every number fits, so this only fails for fields that skipped it
+		let too_large = |mines: usize| Error::new(&fl!("error-numbers-too-large", mines = mines));
+		for i in 0..self.field.len() {
+			let coords = self.index_to_coordintes(i)?;
+			let mines: usize = self
+				.neighbours(&coords)
+				.iter()
+				.filter_map(|c| self.get_tile(c).ok())
+				.map(|t| t.mines as usize)
+				.sum();
+			let sum: i32 = self
+				.rule
+				.counted(&self.geometry, &coords, &self.limit)
+				.iter()
+				.filter_map(|c| self.get_tile(c).ok())
+				.map(|t| self.rule.weight(t.mines, t.negative) as i32)
+				.sum();
+			let lie_up = self.rule == Rule::Liar && rng.ratio(1, 2);
+
+			self.field[i].value = u8::try_from(mines).map_err(|_| too_large(mines))?;
+			let sum = i16::try_from(sum).map_err(|_| too_large(mines))?;
+			self.field[i].shown = self.rule.shown(sum, lie_up);
+		}
+		Ok(())
+	}
+
 	pub fn size(&self) -> usize {
 		self.field.len()
 	}
 
 	pub fn print_revealed(&self) {
+		let labels: Vec<String> = self
+			.field
+			.iter()
+			.map(|cell| {
+				if !cell.is_mine {
+					cell.shown.to_string()
+				} else {
+					mine_label(cell)
+				}
+			})
+			.collect();
+		let width = labels.iter().map(|l| l.len()).max().unwrap_or(1);
+
 		let mut to_write = String::new();
-		let mut itoa = itoa::Buffer::new();
-		for cell in self.field.iter().enumerate() {
-			to_write += "[";
-			if !cell.1.is_mine {
-				to_write += itoa.format(cell.1.value);
-			} else {
-				to_write += "M";
+		for z in 0..self.limit.z {
+			if self.limit.z > 1 {
+				to_write += &self.describe_layer(z);
+				to_write += "\n";
 			}
-			to_write += "]";
-			let coords = self.index_to_coordintes(cell.0).unwrap();
-
-			if coords.0 == self.limit.x - 1 {
+			for y in 0..self.limit.y {
+				to_write += &self.row_indent(y, width);
+				for x in 0..self.limit.x {
+					let label = &labels[self.get_index(&Coordintes { x, y, z }).unwrap()];
+					to_write += &format!("[{:>width$}]", label, width = width);
+					to_write += self.tile_gap();
+				}
 				to_write += "\n";
 			}
 		}
@@ -244,9 +651,120 @@ impl Field {
 		println!("{}", to_write);
 	}
 
+	// heading shown above each layer of three dimensional fields
+	pub fn describe_layer(&self, z: u8) -> String {
+		fl!("layer", layer = (z + 1), layers = self.limit.z)
+	}
+
+	// number of mines and flags, together with the legend of the geometry
+	pub fn summary(&self) -> String {
+		let mut summary = fl!(
+			"field-mines",
+			flags = self.num_flags(),
+			mines = self.get_num_mines()
+		);
+		for line in self.legend() {
+			summary += "\n";
+			summary += &line;
+		}
+		summary
+	}
+
+	// explains everything differing from the classic game, one entry per topic
+	pub fn legend(&self) -> Vec<String> {
+		let mut legend = Vec::new();
+		if let Some(geometry) = describe_geometry(&self.geometry) {
+			legend.push(geometry);
+		}
+		if self.mines_per_tile > 1 {
+			legend.push(fl!("mines-per-tile-rule", count = self.mines_per_tile));
+		}
+		match self.rule {
+			Rule::Classic => (),
+			Rule::Liar => legend.push(fl!("rule-liar-legend")),
+			Rule::Double => legend.push(fl!("rule-double-legend")),
+			Rule::Negative => legend.push(fl!("rule-negative-legend")),
+			Rule::Orthogonal => legend.push(fl!("rule-orthogonal-legend")),
+		}
+		if self.field.iter().any(|t| t.owner.is_some()) {
+			legend.push(fl!("versus-legend"));
+		}
+		legend
+	}
+
+	// draws a single layer with column and row numbers, without a trailing newline
+	// all cells are as wide as the widest one, numbers can have multiple digits with large neighbourhoods
+	pub fn layer_to_string(&self, z: u8) -> String {
+		let mut labels = Vec::new();
+		for y in 0..self.limit.y {
+			for x in 0..self.limit.x {
+				let cell = &self.field[self.get_index(&Coordintes { x, y, z }).unwrap()];
+				let label = if cell.flags > 1 {
+					format!("F{}", cell.flags)
+				} else if cell.flag {
+					String::from("F")
+				} else if cell.unknown {
+					String::from("?")
+				} else if cell.revealed {
+					if let Some(player) = cell.owner {
+						format!("P{}", player + 1)
+					} else if cell.is_mine {
+						mine_label(cell)
+					} else {
+						cell.shown.to_string()
+					}
+				} else {
+					String::from(" ")
+				};
+				labels.push(label);
+			}
+		}
+		let width = labels.iter().map(|l| l.len()).max().unwrap_or(1);
+		let row_label_width = self.limit.y.to_string().len();
+
+		let mut to_write = " ".repeat(row_label_width + 1);
+		for i in 0..self.limit.x {
+			to_write += &format!("{:>width$}|", i + 1, width = width + 1);
+			to_write += self.tile_gap();
+		}
+		to_write += "\n";
+
+		for y in 0..self.limit.y {
+			to_write += &format!("{:>width$}|", y + 1, width = row_label_width);
+			to_write += &self.row_indent(y, width);
+			for x in 0..self.limit.x {
+				let label = &labels[y as usize * self.limit.x as usize + x as usize];
+				to_write += &format!("[{:>width$}]", label, width = width);
+				to_write += self.tile_gap();
+			}
+			if y + 1 < self.limit.y {
+				to_write += "\n";
+			}
+		}
+
+		to_write
+	}
+
+	// hex rows are drawn with a gap between tiles, so shifting odd rows by half a tile lines them up
+	fn tile_gap(&self) -> &'static str {
+		match self.geometry.grid {
+			Grid::Square => "",
+			Grid::Hex => " ",
+		}
+	}
+
+	// half of a cell, which is the label with brackets and the gap
+	fn row_indent(&self, y: u8, width: usize) -> String {
+		match self.geometry.grid {
+			Grid::Hex if y % 2 == 1 => " ".repeat((width + 3) / 2),
+			_ => String::new(),
+		}
+	}
+
+	// every tile has to be revealed, or hold mines and carry exactly as many flags
 	pub fn victory(&self) -> bool {
 		for t in &self.field {
-			if !t.revealed && !t.flag {
+			if !(t.revealed || t.is_mine && t.flags == t.mines) {
 				return false;
 			}
 		}
@@ -257,9 +775,7 @@ impl Field {
 	pub fn num_flags(&self) -> u16 {
 		let mut num_flags = 0;
 		for t in &self.field {
-			if t.flag {
-				num_flags += 1;
-			}
+			num_flags += t.flags as u16;
 		}
 
 		num_flags
@@ -268,48 +784,58 @@ impl Field {
 
 impl Display for Field {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		writeln!(f, "Mines: {}/{}", self.num_flags(), self.num_mines)?;
-		let mut to_write = String::from("  ");
-		let mut itoa = itoa::Buffer::new();
+		writeln!(f, "{}", self.summary())?;
+		if self.limit.z == 1 {
+			return write!(f, "{}", self.layer_to_string(0));
+		}
 
-		for i in 0..self.limit.x {
-			to_write += " ";
-			to_write += itoa.format(i + 1);
-			to_write += "|";
-		}
-
-		to_write += "\n1|";
-
-		let mut y: u8 = 1;
-		for cell in self.field.iter().enumerate() {
-			to_write += "[";
-			if cell.1.flag {
-				to_write += "F"
-			} else if cell.1.unknown {
-				to_write += "?"
-			} else if cell.1.revealed {
-				if cell.1.is_mine {
-					to_write += "M"
-				} else {
-					to_write += itoa.format(cell.1.value);
-				}
-			} else {
-				to_write += " ";
+		for z in 0..self.limit.z {
+			writeln!(f, "{}", self.describe_layer(z))?;
+			write!(f, "{}", self.layer_to_string(z))?;
+			if z + 1 < self.limit.z {
+				writeln!(f)?;
 			}
-			to_write += "]";
-			let coords = self.index_to_coordintes(cell.0).unwrap();
+		}
 
-			if coords.0 == self.limit.x - 1 {
-				to_write += "\n";
-				if self.limit.y > y {
-					y += 1;
-					to_write += itoa.format(y);
-					to_write += "|";
-				}
-			}
+		Ok(())
+	}
+}
+
+// multiple mines in one tile are shown with their count, negative ones with a minus
+fn mine_label(tile: &Tile) -> String {
+	let sign = if tile.negative { "-" } else { "" };
+	if tile.mines > 1 {
+		format!("{}M{}", sign, tile.mines)
+	} else {
+		format!("{}M", sign)
+	}
+}
+
+// explains how tiles are laid out and which edges are connected, None for the classic square rectangle
+pub fn describe_geometry(geometry: &Geometry) -> Option<String> {
+	let mut parts = Vec::new();
+	if geometry.grid == Grid::Hex {
+		parts.push(fl!("grid-hex-rows"));
+	}
+	match &geometry.neighbourhood {
+		Neighbourhood::Moore => (),
+		Neighbourhood::VonNeumann => parts.push(fl!("neighbourhood-von-neumann-rule")),
+		Neighbourhood::Knight => parts.push(fl!("neighbourhood-knight-rule")),
+		Neighbourhood::Radius2 => parts.push(fl!("neighbourhood-radius-2-rule")),
+		Neighbourhood::Custom(offsets) => {
+			parts.push(fl!("neighbourhood-custom-rule", count = offsets.len()))
 		}
+	}
+	match geometry.topology {
+		Topology::Rectangle => (),
+		Topology::Cylinder => parts.push(fl!("topology-cylinder-edges")),
+		Topology::Torus => parts.push(fl!("topology-torus-edges")),
+	}
 
-		write!(f, "{}", to_write)
+	if parts.is_empty() {
+		None
+	} else {
+		Some(parts.join(" "))
 	}
 }
 
@@ -328,7 +854,7 @@ mod field_tests {
 		let f = Field::new(255, 255, 1);
 		for y in 0..255 {
 			for x in 0..255 {
-				let c = Coordintes { x, y };
+				let c = Coordintes { x, y, z: 0 };
 				let i = f.get_index(&c)?;
 				v.push(i);
 			}
@@ -376,7 +902,7 @@ mod field_tests {
 			.unwrap();
 		for (i, a) in arr.iter_mut().enumerate().take(255 * 255) {
 			let c = f.index_to_coordintes(i)?;
-			*a = (c.0 as i16, c.1 as i16);
+			*a = (c.x as i16, c.y as i16);
 		}
 
 		if arr[255 * 255 - 1] == (0, 0) {
@@ -414,4 +940,551 @@ mod field_tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_safe_opening() -> Result<(), Error> {
+		let start = Coordintes { x: 4, y: 4, z: 0 };
+		for seed in 0..100 {
+			let mut f = Field::new(9, 9, 70);
+			f.set_safe_start(SafeStart::Opening);
+			f.init(&start, seed)?;
+
+			if f.is_mine(&start)? || f.get_value(start)? != 0 {
+				return Err(Error::new("first click did not open an area"));
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_safe_relocate() -> Result<(), Error> {
+		let first = Coordintes { x: 0, y: 0, z: 0 };
+		let second = Coordintes { x: 5, y: 6, z: 0 };
+		for seed in 0..100 {
+			let mut a = Field::new(9, 9, 30);
+			a.set_safe_start(SafeStart::Relocate);
+			a.init(&first, seed)?;
+			let mut b = Field::new(9, 9, 30);
+			b.set_safe_start(SafeStart::Relocate);
+			b.init(&second, seed)?;
+
+			if a.is_mine(&first)? || b.is_mine(&second)? {
+				return Err(Error::new("first click hit a mine"));
+			}
+
+			// the layout does not depend on the click, apart from the clicked tiles and where their mines went
+			let moved = a
+				.get_field()
+				.iter()
+				.zip(b.get_field())
+				.filter(|(x, y)| x.mines != y.mines)
+				.count();
+			let total: usize = a.get_field().iter().map(|t| t.mines as usize).sum();
+			if moved > 4 || total != 30 {
+				return Err(Error::new("mines were not relocated"));
+			}
+		}
+
+		Ok(())
+	}
+
+	// every tile apart from the clicked one should get mines equally often, also on fields higher than wide
+	#[test]
+	fn test_placement_distribution() -> Result<(), Error> {
+		const SEEDS: u64 = 3000;
+		const MINES: u16 = 40;
+		let start = Coordintes { x: 0, y: 0, z: 0 };
+		let mut counts = vec![0u32; 10 * 20];
+		for seed in 0..SEEDS {
+			let mut f = Field::new(10, 20, MINES);
+			f.init(&start, seed)?;
+			for (i, tile) in f.get_field().iter().enumerate() {
+				counts[i] += tile.mines as u32;
+			}
+		}
+
+		if counts[0] != 0 {
+			return Err(Error::new("mine placed on the clicked tile"));
+		}
+
+		// chi-squared test with 198 degrees of freedom, failing it by chance is far below one in a million
+		let expected = (SEEDS * MINES as u64) as f64 / (counts.len() - 1) as f64;
+		let chi: f64 = counts[1..]
+			.iter()
+			.map(|c| (*c as f64 - expected).powi(2) / expected)
+			.sum();
+		if chi > 320.0 {
+			return Err(Error::new("mines are not placed uniformly"));
+		}
+
+		Ok(())
+	}
+
+	// mines of a 9x9 field with 10 mines started in the middle
+	const GOLDEN_SEED_0: [usize; 10] = [12, 13, 15, 26, 32, 62, 67, 69, 75, 79];
+	const GOLDEN_SEED_42: [usize; 10] = [6, 7, 16, 21, 22, 39, 51, 63, 67, 80];
+
+	// a shared seed has to keep giving the same field, changing this means bumping PLACEMENT_VERSION
+	#[test]
+	fn test_golden_layouts() -> Result<(), Error> {
+		let start = Coordintes { x: 4, y: 4, z: 0 };
+		for (seed, golden) in [(0, GOLDEN_SEED_0), (42, GOLDEN_SEED_42)] {
+			let mut f = Field::new(9, 9, 10);
+			f.init(&start, seed)?;
+			let mines: Vec<usize> = (0..f.size())
+				.filter(|i| f.get_field()[*i].is_mine)
+				.collect();
+			if mines != golden {
+				return Err(Error::new("seed gives a different field"));
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_placement_version() -> Result<(), Error> {
+		let start = Coordintes { x: 0, y: 0, z: 0 };
+		let mut f = Field::new(9, 9, 10);
+		f.set_placement(PLACEMENT_VERSION + 1);
+		if f.init(&start, 0).is_ok() {
+			return Err(Error::new("unknown placement version accepted"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_values_match_mines() -> Result<(), Error> {
+		for grid in [Grid::Square, Grid::Hex] {
+			for topology in [Topology::Rectangle, Topology::Cylinder, Topology::Torus] {
+				for seed in 0..20 {
+					let mut f = Field::new(8, 10, 20);
+					f.set_geometry(Geometry {
+						grid,
+						topology,
+						..Geometry::default()
+					});
+					f.init(&Coordintes { x: 0, y: 0, z: 0 }, seed)?;
+
+					for y in 0..10 {
+						for x in 0..8 {
+							let c = Coordintes { x, y, z: 0 };
+							let sur = f.neighbours(&c);
+							let most = match grid {
+								Grid::Square => 8,
+								Grid::Hex => 6,
+							};
+							// only on a torus no tile is at an edge
+							if sur.len() > most
+								|| (topology == Topology::Torus && sur.len() != most)
+							{
+								return Err(Error::new("wrong number of neighbours"));
+							}
+
+							let mut mines = 0;
+							for s in sur {
+								if f.is_mine(&s)? {
+									mines += 1;
+								}
+							}
+							if f.get_value(c)? != mines {
+								return Err(Error::new("value does not match surrounding mines"));
+							}
+						}
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_hex_neighbours_symmetric() -> Result<(), Error> {
+		let f = {
+			let mut f = Field::new(7, 6, 1);
+			f.set_geometry(Geometry {
+				grid: Grid::Hex,
+				topology: Topology::Torus,
+				..Geometry::default()
+			});
+			f
+		};
+
+		for y in 0..6 {
+			for x in 0..7 {
+				let c = Coordintes { x, y, z: 0 };
+				for n in f.neighbours(&c) {
+					if !f.neighbours(&n).contains(&c) {
+						eprintln!("{} is next to {}, but not the other way around", n, c);
+						return Err(Error::new("hex neighbours are not symmetric"));
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_hex_wrap_heights() -> Result<(), Error> {
+		let start = Coordintes::new();
+		for topology in [Topology::Rectangle, Topology::Cylinder, Topology::Torus] {
+			for height in 2..=9 {
+				let mut f = Field::new(5, height, 3);
+				f.set_geometry(Geometry {
+					grid: Grid::Hex,
+					topology,
+					..Geometry::default()
+				});
+
+				// odd heights cannot wrap on hex grids, the others have to stay symmetric across the seam
+				let result = f.init(&start, 1);
+				if topology.wraps_y() && height % 2 == 1 {
+					if result.is_ok() {
+						return Err(Error::new("odd hex height wrapped"));
+					}
+					continue;
+				}
+				result?;
+
+				for y in 0..height {
+					for x in 0..5 {
+						let c = Coordintes { x, y, z: 0 };
+						if f.neighbours(&c)
+							.iter()
+							.any(|n| !f.neighbours(n).contains(&c))
+						{
+							return Err(Error::new("hex neighbours are not symmetric"));
+						}
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_3d_field() -> Result<(), Error> {
+		let centre = Coordintes { x: 2, y: 2, z: 1 };
+		for seed in 0..20 {
+			let mut f = Field::new_3d(5, 5, 3, 15);
+			f.init(&Coordintes { x: 0, y: 0, z: 0 }, seed)?;
+
+			if f.neighbours(&centre).len() != 26 {
+				return Err(Error::new(
+					"tile in the middle of a cube does not have 26 neighbours",
+				));
+			}
+
+			for i in 0..f.size() {
+				let c = f.index_to_coordintes(i)?;
+				if f.get_index(&c)? != i {
+					return Err(Error::new("index does not round trip"));
+				}
+
+				let mines = f
+					.neighbours(&c)
+					.iter()
+					.filter(|n| f.is_mine(n).unwrap_or(false))
+					.count();
+				if f.get_value(c)? as usize != mines {
+					return Err(Error::new("value does not match surrounding mines"));
+				}
+			}
+		}
+
+		// without mines a single click has to open every layer
+		let mut f = Field::new_3d(4, 4, 4, 0);
+		let start = Coordintes { x: 0, y: 0, z: 0 };
+		f.init(&start, 0)?;
+		f.reveal(&start)?;
+		if !f.victory() {
+			return Err(Error::new("flood fill did not reach every layer"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_neighbourhoods() -> Result<(), Error> {
+		let rules = [
+			(Neighbourhood::Moore, 8),
+			(Neighbourhood::VonNeumann, 4),
+			(Neighbourhood::Knight, 8),
+			(Neighbourhood::Radius2, 24),
+			("2,0;0,2;2,0".parse()?, 2),
+		];
+		let centre = Coordintes { x: 4, y: 4, z: 0 };
+
+		for (neighbourhood, count) in rules {
+			for seed in 0..20 {
+				let mut f = Field::new(9, 9, 15);
+				f.set_geometry(Geometry {
+					neighbourhood: neighbourhood.clone(),
+					..Geometry::default()
+				});
+				f.init(&Coordintes { x: 0, y: 0, z: 0 }, seed)?;
+
+				if f.neighbours(&centre).len() != count {
+					eprintln!("{:?}", neighbourhood);
+					return Err(Error::new("wrong number of neighbours"));
+				}
+
+				for i in 0..f.size() {
+					let c = f.index_to_coordintes(i)?;
+					let mines = f
+						.neighbours(&c)
+						.iter()
+						.filter(|n| f.is_mine(n).unwrap_or(false))
+						.count();
+					if f.get_value(c)? as usize != mines {
+						return Err(Error::new("value does not match surrounding mines"));
+					}
+				}
+			}
+		}
+
+		if "1,0;x".parse::<Neighbourhood>().is_ok() || "0,0".parse::<Neighbourhood>().is_ok() {
+			return Err(Error::new("invalid offsets were accepted"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_multiple_mines_per_tile() -> Result<(), Error> {
+		let start = Coordintes { x: 0, y: 0, z: 0 };
+		for seed in 0..20 {
+			let mut f = Field::new(6, 6, 60);
+			f.set_mines_per_tile(3);
+			f.init(&start, seed)?;
+
+			let tiles = f.get_field();
+			let total: u16 = tiles.iter().map(|t| t.mines as u16).sum();
+			if total != 60 || tiles.iter().any(|t| t.mines > 3) || f.is_mine(&start)? {
+				return Err(Error::new("mines were not placed as requested"));
+			}
+
+			for i in 0..f.size() {
+				let c = f.index_to_coordintes(i)?;
+				let mines: u8 = f
+					.neighbours(&c)
+					.iter()
+					.map(|n| tiles[f.get_index(n).unwrap()].mines)
+					.sum();
+				if f.get_value(c)? != mines {
+					return Err(Error::new("value does not count every mine"));
+				}
+			}
+
+			// revealing every safe tile is not enough, each mine needs its own flag
+			for i in 0..f.size() {
+				let c = f.index_to_coordintes(i)?;
+				if !f.is_mine(&c)? {
+					f.reveal(&c)?;
+				}
+			}
+			for (i, tile) in tiles.iter().enumerate() {
+				let c = f.index_to_coordintes(i)?;
+				for _ in 0..tile.mines {
+					if f.victory() {
+						return Err(Error::new("won before all mines were flagged"));
+					}
+					f.flag(&c)?;
+				}
+			}
+			if !f.victory() || f.num_flags() != 60 {
+				return Err(Error::new("not won after flagging every mine"));
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_opening_without_room() -> Result<(), Error> {
+		// the 7 tiles outside of the opening only hold 14 mines, so only the clicked tile can stay free
+		let start = Coordintes { x: 1, y: 1, z: 0 };
+		for safe_start in [SafeStart::Opening, SafeStart::NoGuess] {
+			for seed in 0..20 {
+				let mut f = Field::new(4, 4, 15);
+				f.set_mines_per_tile(2);
+				f.set_safe_start(safe_start);
+				f.init(&start, seed)?;
+
+				let total: u16 = f.get_field().iter().map(|t| t.mines as u16).sum();
+				if total != f.get_num_mines() || total != 15 || f.is_mine(&start)? {
+					return Err(Error::new("mines were dropped from the field"));
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_numbers_fit() -> Result<(), Error> {
+		let fits = |mines_per_tile, layers, neighbourhood, rule| {
+			let mut f = Field::new_3d(9, 9, layers, 10);
+			f.set_mines_per_tile(mines_per_tile);
+			f.set_geometry(Geometry {
+				neighbourhood,
+				..Geometry::default()
+			});
+			f.set_rule(rule);
+			f.check().is_ok()
+		};
+
+		// 8 neighbours of 31 mines are 248, 32 mines would be 256
+		if !fits(31, 1, Neighbourhood::Moore, Rule::Double)
+			|| fits(32, 1, Neighbourhood::Moore, Rule::Classic)
+		{
+			return Err(Error::new("moore limit is wrong"));
+		}
+		// 26 neighbours in three dimensions
+		if !fits(9, 3, Neighbourhood::Moore, Rule::Classic)
+			|| fits(10, 3, Neighbourhood::Moore, Rule::Classic)
+		{
+			return Err(Error::new("three dimensional limit is wrong"));
+		}
+		// a single neighbour leaves room for the most mines a tile can hold
+		if !fits(u8::MAX, 1, "1,0".parse()?, Rule::Liar) {
+			return Err(Error::new("single neighbour limit is wrong"));
+		}
+
+		// layouts are checked as well, the middle tile would have 2 neighbours of 255 mines
+		let limit = Coordintes { x: 3, y: 1, z: 1 };
+		if Field::from_layout(limit, &[u8::MAX, 0, 1]).is_ok() {
+			return Err(Error::new(
+				"layout with too many mines around a tile accepted",
+			));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_largest_numbers() -> Result<(), Error> {
+		// every tile around the middle is full, 8 tiles of 31 mines counting twice
+		let middle = Coordintes { x: 1, y: 1, z: 0 };
+		let mut f = Field::new(3, 3, 8 * 31);
+		f.set_mines_per_tile(31);
+		f.set_rule(Rule::Double);
+		f.init(&middle, 0)?;
+		let tile = f.get_tile(&middle)?;
+		if tile.is_mine || tile.value != 248 || tile.shown != 496 {
+			return Err(Error::new("largest numbers were not counted exactly"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_flag_cycle() -> Result<(), Error> {
+		let c = Coordintes::new();
+		let mut f = Field::from_layout(Coordintes { x: 2, y: 1, z: 1 }, &[u8::MAX, 0])?;
+		let most = f.get_mines_per_tile();
+		for _ in 0..most {
+			f.flag(&c)?;
+		}
+		if f.get_tile(&c)?.flags != most {
+			return Err(Error::new(
+				"flags stopped before the most mines a tile holds",
+			));
+		}
+		f.flag(&c)?;
+		if f.get_tile(&c)?.flags != 0 {
+			return Err(Error::new("flags did not cycle back to none"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_rules() -> Result<(), Error> {
+		let start = Coordintes { x: 0, y: 0, z: 0 };
+		for rule in Rule::ALL {
+			for seed in 0..10 {
+				let mut f = Field::new(10, 10, 40);
+				f.set_mines_per_tile(2);
+				f.set_rule(rule);
+				f.init(&start, seed)?;
+
+				let tiles = f.get_field();
+				for i in 0..f.size() {
+					let c = f.index_to_coordintes(i)?;
+					let tile = &tiles[i];
+					let sum: i16 = rule
+						.counted(f.get_geometry(), &c, &f.limit)
+						.iter()
+						.map(|n| &tiles[f.get_index(n).unwrap()])
+						.map(|t| rule.weight(t.mines, t.negative))
+						.sum();
+					let expected = match rule {
+						Rule::Liar => (tile.shown - sum).abs() == 1 && tile.shown >= 0,
+						_ => tile.shown == sum,
+					};
+					if !expected || (tile.negative && rule != Rule::Negative) {
+						return Err(Error::new("shown number does not follow the rule"));
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_liar_does_not_cascade() -> Result<(), Error> {
+		let start = Coordintes { x: 0, y: 0, z: 0 };
+		for seed in 0..10 {
+			let mut f = Field::new(9, 9, 10);
+			f.set_rule(Rule::Liar);
+			f.init(&start, seed)?;
+
+			// a real 0 is never shown as one, so revealing it must not open the tiles around it
+			let empty = (0..f.size())
+				.map(|i| f.index_to_coordintes(i))
+				.collect::<Result<Vec<_>, _>>()?
+				.into_iter()
+				.find(|c| {
+					*c != start
+						&& !f.is_mine(c).unwrap_or(true)
+						&& f.get_value(*c).unwrap_or(1) == 0
+				});
+			let empty = match empty {
+				None => continue,
+				Some(c) => c,
+			};
+			f.reveal(&empty)?;
+			if f.get_field().iter().filter(|t| t.revealed).count() != 1 {
+				return Err(Error::new("lying number opened the tiles around it"));
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_no_guess() -> Result<(), Error> {
+		let start = Coordintes { x: 4, y: 4, z: 0 };
+		// lying numbers hardly ever prove a tile safe without the cascade, so only rules opening areas are checked
+		for rule in [Rule::Classic, Rule::Double] {
+			for seed in 0..5 {
+				let mut f = Field::new(9, 9, 10);
+				f.set_rule(rule);
+				f.set_safe_start(SafeStart::NoGuess);
+				f.init(&start, seed)?;
+				if !solver::solvable(&f, &start) {
+					return Err(Error::new("no guess field needs guessing"));
+				}
+			}
+		}
+
+		Ok(())
+	}
 }
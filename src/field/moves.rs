@@ -0,0 +1,91 @@
+use super::{tile::Coordintes, Field};
+use crate::error::Error;
+
+// what a player can do to a single tile
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+	Reveal,
+	Flag,
+	Unknown,
+	Chord,
+}
+
+// how a game played alone stands
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Progress {
+	Playing,
+	Won,
+	Lost,
+}
+
+/*
+Makes one move of a game played alone, the same way for every frontend.
+The first reveal places the mines from the seed, fields that already have their mines ignore it
+*/
+pub fn play(
+	field: &mut Field,
+	action: Move,
+	coords: &Coordintes,
+	seed: u64,
+) -> Result<Progress, Error> {
+	match action {
+		Move::Reveal => {
+			if !field.is_initialized() {
+				field.init(coords, seed)?;
+			}
+			field.reveal(coords)?;
+		}
+		Move::Flag => field.flag(coords)?,
+		Move::Unknown => field.mark_unknown(coords)?,
+		Move::Chord => {
+			field.chord(coords)?;
+		}
+	}
+	Ok(progress(field))
+}
+
+pub fn progress(field: &Field) -> Progress {
+	if field.has_lost() {
+		Progress::Lost
+	} else if field.is_initialized() && field.victory() {
+		Progress::Won
+	} else {
+		Progress::Playing
+	}
+}
+
+#[cfg(test)]
+mod moves_tests {
+	use super::*;
+
+	#[test]
+	fn test_play() -> Result<(), Error> {
+		let limit = Coordintes { x: 3, y: 3, z: 1 };
+		let at = |x, y| Coordintes { x, y, z: 0 };
+
+		let mut field = Field::from_layout(limit, &[1, 0, 0, 0, 0, 0, 0, 0, 0])?;
+		if play(&mut field, Move::Flag, &at(0, 0), 0)? != Progress::Playing {
+			return Err(Error::new("flag ended the game"));
+		}
+		if play(&mut field, Move::Reveal, &at(2, 2), 0)? != Progress::Won {
+			return Err(Error::new("clearing the board did not win"));
+		}
+
+		let mut field = Field::from_layout(limit, &[1, 0, 0, 0, 0, 0, 0, 0, 0])?;
+		play(&mut field, Move::Reveal, &at(1, 1), 0)?;
+		play(&mut field, Move::Flag, &at(0, 1), 0)?;
+		if play(&mut field, Move::Chord, &at(1, 1), 0)? != Progress::Lost {
+			return Err(Error::new("chording around a wrong flag did not lose"));
+		}
+
+		// the first reveal places the mines somewhere else
+		let mut field = Field::new(9, 9, 10);
+		if play(&mut field, Move::Reveal, &at(4, 4), 7)? != Progress::Playing
+			|| !field.is_initialized()
+		{
+			return Err(Error::new("first reveal did not start the game"));
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,245 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use super::{solver, tile::Coordintes, Field};
+use crate::{config::data_dir, error::Error, fl};
+
+// files with this extension are listed as puzzles
+pub const EXTENSION: &str = "puzzle";
+
+/*
+A hand made board where some tiles are already revealed, solved by logic alone.
+Puzzle files have one line per row:
+. is a hidden empty tile, * a mine and o a revealed tile,
+a digit is a revealed tile as well, but the number is checked against the mines around it.
+Lines starting with # are comments, "# name: ..." gives the puzzle a name
+*/
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+	pub name: String,
+	pub field: Field,
+}
+
+impl Puzzle {
+	// the name is used if the text does not contain one
+	pub fn parse(text: &str, name: &str) -> Result<Puzzle, Error> {
+		let mut name = name.to_string();
+		let mut rows = Vec::new();
+		for line in text.lines().map(str::trim) {
+			if let Some(comment) = line.strip_prefix('#') {
+				if let Some(n) = comment.trim().strip_prefix("name:") {
+					name = n.trim().to_string();
+				}
+			} else if !line.is_empty() {
+				rows.push(line);
+			}
+		}
+
+		let width = rows.first().map(|r| r.chars().count()).unwrap_or_default();
+		if width > u8::MAX as usize
+			|| rows.len() > u8::MAX as usize
+			|| rows.iter().any(|r| r.chars().count() != width)
+		{
+			return Err(Error::new(&fl!("error-layout-size")));
+		}
+
+		let mut mines = Vec::new();
+		let mut revealed = Vec::new();
+		// numbers given in the text, checked once the field is built
+		let mut numbers = Vec::new();
+		for (y, row) in rows.iter().enumerate() {
+			for (x, c) in row.chars().enumerate() {
+				let (mine, open) = match c {
+					'*' => (1, false),
+					'.' => (0, false),
+					'o' => (0, true),
+					'0'..='9' => {
+						let coords = Coordintes {
+							x: x as u8,
+							y: y as u8,
+							z: 0,
+						};
+						numbers.push((coords, c.to_digit(10)));
+						(0, true)
+					}
+					_ => {
+						return Err(Error::new(&fl!(
+							"error-puzzle-character",
+							character = c.to_string()
+						)))
+					}
+				};
+				mines.push(mine);
+				revealed.push(open);
+			}
+		}
+
+		let limit = Coordintes {
+			x: width as u8,
+			y: rows.len() as u8,
+			z: 1,
+		};
+		let puzzle = Puzzle::from_parts(&name, limit, &mines, &revealed)?;
+		for (coords, number) in numbers {
+			let value = puzzle.field.get_tile(&coords)?.value;
+			if number.is_some_and(|n| n != value as u32) {
+				return Err(Error::new(&fl!(
+					"error-puzzle-number",
+					row = (coords.y + 1),
+					column = (coords.x + 1)
+				)));
+			}
+		}
+
+		Ok(puzzle)
+	}
+
+	// mines and revealed tiles are in the order of get_index, revealed tiles must not hold a mine
+	pub fn from_parts(
+		name: &str,
+		limit: Coordintes,
+		mines: &[u8],
+		revealed: &[bool],
+	) -> Result<Puzzle, Error> {
+		let mut field = Field::from_layout(limit, mines)?;
+		for (i, open) in revealed.iter().enumerate() {
+			if !open {
+				continue;
+			}
+			let coords = field.index_to_coordintes(i)?;
+			if mines.get(i).is_some_and(|m| *m > 0) {
+				return Err(Error::new(&fl!(
+					"error-puzzle-revealed-mine",
+					row = (coords.y + 1),
+					column = (coords.x + 1)
+				)));
+			}
+			field.reveal(&coords)?;
+		}
+
+		Ok(Puzzle {
+			name: name.to_string(),
+			field,
+		})
+	}
+
+	// the text parse reads, revealed tiles are written as their numbers so they are checked when read again
+	pub fn to_text(&self) -> String {
+		let limit = self.field.get_limit();
+		let tiles = self.field.get_field();
+		let mut text = format!("# name: {}\n", self.name);
+		for row in tiles.chunks(limit.x.max(1) as usize).take(limit.y as usize) {
+			for tile in row {
+				text.push(match tile {
+					t if t.is_mine => '*',
+					t if t.revealed && t.value <= 9 => (b'0' + t.value) as char,
+					t if t.revealed => 'o',
+					_ => '.',
+				});
+			}
+			text.push('\n');
+		}
+		text
+	}
+
+	// named after the file, unless the file names itself
+	pub fn load(path: &Path) -> Result<Puzzle, Error> {
+		let text = fs::read_to_string(path).map_err(|e| Error::new(&e.to_string()))?;
+		let name = path
+			.file_stem()
+			.map(|s| s.to_string_lossy().into_owned())
+			.unwrap_or_default();
+		Puzzle::parse(&text, &name)
+	}
+
+	/*
+	The solver only makes deductions that hold for every possible layout,
+	so if it finishes the puzzle there is exactly one solution.
+	Puzzles it cannot finish might still be unique, but need more than it knows
+	*/
+	pub fn is_unique(&self) -> bool {
+		solver::solvable_from_here(&self.field)
+	}
+}
+
+// $XDG_DATA_HOME/<app id>/puzzles
+pub fn default_dir() -> Option<PathBuf> {
+	Some(data_dir()?.join("puzzles"))
+}
+
+// a file of the puzzle directory, files that fail to load are kept with their error
+pub type PuzzleFile = (PathBuf, Result<Puzzle, Error>);
+
+// every puzzle in the directory sorted by file name
+pub fn load_dir(dir: &Path) -> Result<Vec<PuzzleFile>, Error> {
+	let entries = fs::read_dir(dir).map_err(|e| Error::new(&e.to_string()))?;
+	let mut paths: Vec<PathBuf> = entries
+		.filter_map(|e| e.ok())
+		.map(|e| e.path())
+		.filter(|p| p.extension().is_some_and(|e| e == EXTENSION))
+		.collect();
+	paths.sort();
+
+	Ok(paths
+		.into_iter()
+		.map(|p| {
+			let puzzle = Puzzle::load(&p);
+			(p, puzzle)
+		})
+		.collect())
+}
+
+#[cfg(test)]
+mod puzzle_tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() -> Result<(), Error> {
+		let text = "# name: Corner\n# a comment\n1*.\n111\no..\n";
+		let puzzle = Puzzle::parse(text, "fallback")?;
+		if puzzle.name != "Corner" || puzzle.field.get_num_mines() != 1 {
+			return Err(Error::new("puzzle read wrongly"));
+		}
+
+		let revealed = puzzle
+			.field
+			.get_field()
+			.iter()
+			.filter(|t| t.revealed)
+			.count();
+		// the o has no mines around, so it opens the empty area next to it
+		if revealed != 7 || !puzzle.field.is_initialized() {
+			return Err(Error::new("revealed tiles not revealed"));
+		}
+
+		if Puzzle::parse("2*.\n...\n", "").is_ok() {
+			return Err(Error::new("wrong number accepted"));
+		}
+
+		let again = Puzzle::parse(&puzzle.to_text(), "")?;
+		if again.name != puzzle.name || again.field.get_field() != puzzle.field.get_field() {
+			return Err(Error::new("puzzle changed while being written and read"));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_uniqueness() -> Result<(), Error> {
+		// the 1 at the left can only be explained by the tile above it
+		let unique = Puzzle::parse("*..\n1oo\n", "")?;
+		if !unique.is_unique() {
+			return Err(Error::new("unique puzzle not solved"));
+		}
+
+		// both 1s see the same two tiles, nothing tells them apart
+		let guess = Puzzle::parse("*.\noo\n", "")?;
+		if guess.is_unique() {
+			return Err(Error::new("puzzle needing a guess counted as unique"));
+		}
+
+		Ok(())
+	}
+}
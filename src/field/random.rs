@@ -0,0 +1,79 @@
+use std::ops::Range;
+
+use rand_chacha::{
+	rand_core::{RngCore, SeedableRng},
+	ChaCha8Rng,
+};
+
+/*
+Random numbers used to generate fields, so a seed gives the same field on every platform and release.
+StdRng and the sampling helpers of rand are allowed to change between versions of rand,
+so this uses ChaCha8 with the seed in its key and does the sampling itself.
+Changing anything here changes every field, see PLACEMENT_VERSION
+*/
+pub struct FieldRng(ChaCha8Rng);
+
+impl FieldRng {
+	pub fn new(seed: u64) -> FieldRng {
+		// the seed takes the first 8 bytes of the key in little endian, the rest stays 0
+		let mut key = [0; 32];
+		key[..8].copy_from_slice(&seed.to_le_bytes());
+		FieldRng(ChaCha8Rng::from_seed(key))
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.0.next_u64()
+	}
+
+	// uniform in 0..n, numbers from the uneven rest at the top are thrown away instead of skewing the result
+	pub fn below(&mut self, n: u64) -> u64 {
+		let limit = u64::MAX - u64::MAX % n;
+		loop {
+			let v = self.next_u64();
+			if v < limit {
+				return v % n;
+			}
+		}
+	}
+
+	pub fn range(&mut self, range: Range<usize>) -> usize {
+		range.start + self.below((range.end - range.start) as u64) as usize
+	}
+
+	// true numerator out of denominator times
+	pub fn ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+		self.below(denominator) < numerator
+	}
+}
+
+#[cfg(test)]
+mod random_tests {
+	use super::*;
+	use crate::error::Error;
+
+	// the first block of ChaCha8 with a zero key, as in the reference test vectors
+	const GOLDEN_RAW: [u64; 3] = [
+		15438444565445410878,
+		11647726043916688255,
+		4287315583106450476,
+	];
+	const GOLDEN_BELOW: [u64; 8] = [9, 2, 0, 4, 3, 5, 0, 9];
+
+	// these numbers must never change, fields shared by seed depend on them
+	#[test]
+	fn test_golden_values() -> Result<(), Error> {
+		let mut rng = FieldRng::new(0);
+		let raw: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+		if raw != GOLDEN_RAW {
+			return Err(Error::new("raw numbers changed"));
+		}
+
+		let mut rng = FieldRng::new(12345);
+		let below: Vec<u64> = (0..8).map(|_| rng.below(10)).collect();
+		if below != GOLDEN_BELOW {
+			return Err(Error::new("sampled numbers changed"));
+		}
+
+		Ok(())
+	}
+}
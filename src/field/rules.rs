@@ -0,0 +1,119 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::{
+	tile::Coordintes,
+	topology::{Geometry, Neighbourhood},
+};
+
+// twists on how the shown numbers are computed from the mines around a tile
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum Rule {
+	// numbers are the mines around the tile
+	#[default]
+	Classic,
+	// every number is off by exactly one, either up or down
+	Liar,
+	// every mine counts twice
+	Double,
+	// some mines count as -1, so numbers can be negative
+	Negative,
+	// numbers only count the tiles above, below, left and right, no matter which tiles are neighbours
+	Orthogonal,
+}
+
+impl Rule {
+	pub const ALL: [Rule; 5] = [
+		Rule::Classic,
+		Rule::Liar,
+		Rule::Double,
+		Rule::Negative,
+		Rule::Orthogonal,
+	];
+
+	// tiles whose mines are part of the number of the given tile
+	pub fn counted(
+		&self,
+		geometry: &Geometry,
+		coords: &Coordintes,
+		limit: &Coordintes,
+	) -> Vec<Coordintes> {
+		match self {
+			Rule::Orthogonal => Geometry {
+				neighbourhood: Neighbourhood::VonNeumann,
+				..geometry.clone()
+			}
+			.neighbours(coords, limit),
+			_ => geometry.neighbours(coords, limit),
+		}
+	}
+
+	// the most neighbours counted by a number, see counted
+	pub fn max_counted(&self, geometry: &Geometry, limit: &Coordintes) -> usize {
+		match self {
+			Rule::Orthogonal => Geometry {
+				neighbourhood: Neighbourhood::VonNeumann,
+				..geometry.clone()
+			}
+			.max_neighbours(limit),
+			_ => geometry.max_neighbours(limit),
+		}
+	}
+
+	// the most a single mine adds to a number, negative mines take away just as much
+	pub fn max_weight(&self) -> i16 {
+		match self {
+			Rule::Double => 2,
+			_ => 1,
+		}
+	}
+
+	// what a tile with the given mines adds to the numbers around it
+	pub fn weight(&self, mines: u8, negative: bool) -> i16 {
+		match self {
+			Rule::Double => 2 * mines as i16,
+			Rule::Negative if negative => -(mines as i16),
+			_ => mines as i16,
+		}
+	}
+
+	// the number shown for the real sum, lie_up decides in which direction a liar is off
+	pub fn shown(&self, sum: i16, lie_up: bool) -> i16 {
+		match self {
+			Rule::Liar if sum == 0 || lie_up => sum + 1,
+			Rule::Liar => sum - 1,
+			_ => sum,
+		}
+	}
+
+	// every real sum that can result in the shown number
+	pub fn possible_sums(&self, shown: i16) -> Vec<i16> {
+		match self {
+			Rule::Liar if shown == 0 => vec![1],
+			Rule::Liar => vec![shown - 1, shown + 1],
+			_ => vec![shown],
+		}
+	}
+
+	// what a hidden tile could add to the numbers around it, the first entry is always the tile being empty
+	pub fn possible_weights(&self, mines_per_tile: u8) -> Vec<i16> {
+		let mut weights: Vec<i16> = (0..=mines_per_tile)
+			.map(|m| self.weight(m, false))
+			.collect();
+		if *self == Rule::Negative {
+			weights.extend((1..=mines_per_tile).map(|m| self.weight(m, true)));
+		}
+		weights
+	}
+
+	// whether the shown number proves every counted tile to be free of mines, so revealing the tile opens them as well
+	// a liar never shows 0 around no mines and negative mines can cancel each other out, so their numbers prove nothing
+	pub fn proves_empty(&self, shown: i16) -> bool {
+		shown == 0 && !matches!(self, Rule::Liar | Rule::Negative)
+	}
+
+	// chording needs the flags to explain the number exactly, which is impossible if numbers lie or flags could be negative
+	pub fn allows_chord(&self) -> bool {
+		!matches!(self, Rule::Liar | Rule::Negative)
+	}
+}
@@ -0,0 +1,165 @@
+use std::{collections::BTreeMap, thread};
+
+use super::{analysis::analyze, solver, tile::Coordintes, Field};
+use crate::{error::Error, fl};
+
+// how one game played by the solver went
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameResult {
+	pub seed: u64,
+	pub won: bool,
+	// guesses made after the first click, the first click itself is free
+	pub guesses: u32,
+	pub bbbv: usize,
+}
+
+/*
+Plays the field like a careful player: the first click in the middle,
+then every safe tile the solver finds, guessing the least dangerous tile only when nothing is left.
+Games are won once every safe tile is revealed, flags are never placed
+*/
+pub fn play(template: &Field, seed: u64) -> Result<GameResult, Error> {
+	let mut field = template.clone();
+	let limit = field.get_limit();
+	let start = Coordintes {
+		x: limit.x / 2,
+		y: limit.y / 2,
+		z: 0,
+	};
+	field.init(&start, seed)?;
+	let bbbv = analyze(&field).bbbv;
+	field.reveal(&start)?;
+
+	let mut guesses = 0;
+	let cleared = |f: &Field| f.field.iter().all(|t| t.revealed || t.is_mine);
+	while !field.has_lost() && !cleared(&field) {
+		let safe: Vec<Coordintes> = solver::deduce(&field)
+			.into_iter()
+			.filter_map(|d| match d {
+				solver::Deduction::Safe(c) => Some(c),
+				solver::Deduction::Mine(_) => None,
+			})
+			.collect();
+
+		if safe.is_empty() {
+			match solver::guess(&field) {
+				None => break,
+				Some(c) => {
+					guesses += 1;
+					field.reveal(&c)?;
+				}
+			}
+		}
+		for c in safe {
+			field.reveal(&c)?;
+		}
+	}
+
+	Ok(GameResult {
+		seed,
+		won: !field.has_lost() && cleared(&field),
+		guesses,
+		bbbv,
+	})
+}
+
+// plays one game for every seed, spread over the given number of threads, results are in the order of the seeds
+pub fn simulate(template: &Field, seeds: &[u64], threads: usize) -> Result<Vec<GameResult>, Error> {
+	let chunk = seeds.len().div_ceil(threads.max(1)).max(1);
+	thread::scope(|scope| {
+		let workers: Vec<_> = seeds
+			.chunks(chunk)
+			.map(|chunk| {
+				scope.spawn(move || {
+					chunk
+						.iter()
+						.map(|seed| play(template, *seed))
+						.collect::<Result<Vec<GameResult>, Error>>()
+				})
+			})
+			.collect();
+
+		let mut results = Vec::with_capacity(seeds.len());
+		for worker in workers {
+			match worker.join() {
+				Ok(r) => results.extend(r?),
+				Err(_) => return Err(Error::new(&fl!("error-thread"))),
+			}
+		}
+		Ok(results)
+	})
+}
+
+// what a batch of games adds up to
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+	pub games: usize,
+	pub won: usize,
+	pub win_rate: f64,
+	pub average_guesses: f64,
+	// how many games had each 3BV
+	pub bbbv: BTreeMap<usize, usize>,
+}
+
+impl Summary {
+	pub fn new(results: &[GameResult]) -> Summary {
+		let games = results.len();
+		let won = results.iter().filter(|r| r.won).count();
+		let guesses: u64 = results.iter().map(|r| r.guesses as u64).sum();
+
+		let mut bbbv = BTreeMap::new();
+		for r in results {
+			*bbbv.entry(r.bbbv).or_insert(0) += 1;
+		}
+
+		Summary {
+			games,
+			won,
+			win_rate: won as f64 / games.max(1) as f64,
+			average_guesses: guesses as f64 / games.max(1) as f64,
+			bbbv,
+		}
+	}
+
+	pub fn bbbv_min(&self) -> usize {
+		self.bbbv.keys().next().copied().unwrap_or_default()
+	}
+
+	pub fn bbbv_max(&self) -> usize {
+		self.bbbv.keys().next_back().copied().unwrap_or_default()
+	}
+
+	pub fn bbbv_mean(&self) -> f64 {
+		let total: usize = self.bbbv.iter().map(|(b, n)| b * n).sum();
+		total as f64 / self.games.max(1) as f64
+	}
+}
+
+#[cfg(test)]
+mod simulation_tests {
+	use super::*;
+
+	#[test]
+	fn test_simulation() -> Result<(), Error> {
+		let template = Field::new(9, 9, 10);
+		let seeds: Vec<u64> = (0..60).collect();
+
+		// splitting the work must not change anything
+		let single = simulate(&template, &seeds, 1)?;
+		let parallel = simulate(&template, &seeds, 4)?;
+		if single != parallel || single.len() != seeds.len() {
+			return Err(Error::new("threads changed the results"));
+		}
+
+		let summary = Summary::new(&single);
+		// beginner boards are mostly won, even when guessing
+		if summary.win_rate < 0.5 || summary.bbbv_min() == 0 {
+			return Err(Error::new("solver plays badly"));
+		}
+		if single.iter().any(|r| !r.won && r.guesses == 0) {
+			return Err(Error::new("game lost without guessing"));
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{tile::Coordintes, Field};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Deduction {
+	Safe(Coordintes),
+	Mine(Coordintes),
+}
+
+/*
+Only uses information the player can see, flags placed by the player are ignored since they might be wrong.
+Every hidden tile starts out with everything it could add to the numbers around it, which depends on the rule.
+Every revealed number is then checked on its own: options of a hidden neighbour are dropped
+if no combination of the other neighbours can make up the number with them.
+A tile only left with being empty is safe, a tile which can no longer be empty holds mines.
+This is repeated until nothing changes
+*/
+pub fn deduce(field: &Field) -> Vec<Deduction> {
+	let rule = field.get_rule();
+	let weights = rule.possible_weights(field.get_mines_per_tile());
+	let mut options: HashMap<Coordintes, Vec<i16>> = HashMap::new();
+	let mut deductions = Vec::new();
+
+	let mut changed = true;
+	while changed {
+		changed = false;
+
+		for (index, tile) in field.field.iter().enumerate() {
+			if !tile.revealed || tile.is_mine {
+				continue;
+			}
+
+			let coords = match field.index_to_coordintes(index) {
+				Err(_) => continue,
+				Ok(c) => c,
+			};
+
+			let hidden: Vec<Coordintes> = rule
+				.counted(field.get_geometry(), &coords, &field.limit)
+				.into_iter()
+				.filter(|c| !field.already_revealed(c).unwrap_or(true))
+				.collect();
+			if hidden.is_empty() {
+				continue;
+			}
+
+			let sums = rule.possible_sums(tile.shown);
+			let current: Vec<Vec<i16>> = hidden
+				.iter()
+				.map(|c| options.get(c).unwrap_or(&weights).clone())
+				.collect();
+
+			for (i, c) in hidden.iter().enumerate() {
+				// every sum the other hidden neighbours can add up to
+				let mut reachable = HashSet::from([0]);
+				for (j, other) in current.iter().enumerate() {
+					if i != j {
+						reachable = reachable
+							.iter()
+							.flat_map(|r| other.iter().map(move |o| r + o))
+							.collect();
+					}
+				}
+
+				let kept: Vec<i16> = current[i]
+					.iter()
+					.filter(|w| reachable.iter().any(|r| sums.contains(&(r + *w))))
+					.copied()
+					.collect();
+				// nothing being left means the number cannot be explained, which only happens with a broken field
+				if kept.is_empty() || kept.len() == current[i].len() {
+					continue;
+				}
+
+				if kept == [0] {
+					deductions.push(Deduction::Safe(*c));
+				} else if !kept.contains(&0) && current[i].contains(&0) {
+					deductions.push(Deduction::Mine(*c));
+				}
+				options.insert(*c, kept);
+				changed = true;
+			}
+		}
+	}
+
+	deductions
+}
+
+// returns a hidden tile that can be revealed without guessing, if there is one
+pub fn hint(field: &Field) -> Option<Coordintes> {
+	deduce(field).into_iter().find_map(|d| match d {
+		Deduction::Safe(c) => Some(c),
+		Deduction::Mine(_) => None,
+	})
+}
+
+/*
+The hidden tile least likely to hold a mine, for when nothing can be deduced.
+Tiles next to numbers get the share of the number not yet explained by known mines, spread over their undecided neighbours,
+the most dangerous number around a tile counts. All other tiles get the density of the mines left.
+This is an estimate, exact odds would need every arrangement of the mines to be counted
+*/
+pub fn guess(field: &Field) -> Option<Coordintes> {
+	let mines: HashSet<Coordintes> = deduce(field)
+		.into_iter()
+		.filter_map(|d| match d {
+			Deduction::Mine(c) => Some(c),
+			Deduction::Safe(_) => None,
+		})
+		.collect();
+	let rule = field.get_rule();
+
+	let mut risk: HashMap<Coordintes, f64> = HashMap::new();
+	for (index, tile) in field.field.iter().enumerate() {
+		if !tile.revealed || tile.is_mine {
+			continue;
+		}
+		let coords = match field.index_to_coordintes(index) {
+			Err(_) => continue,
+			Ok(c) => c,
+		};
+
+		let hidden: Vec<Coordintes> = rule
+			.counted(field.get_geometry(), &coords, &field.limit)
+			.into_iter()
+			.filter(|c| !field.already_revealed(c).unwrap_or(true))
+			.collect();
+		let undecided: Vec<&Coordintes> = hidden.iter().filter(|c| !mines.contains(c)).collect();
+		if undecided.is_empty() {
+			continue;
+		}
+
+		let known = (hidden.len() - undecided.len()) as f64;
+		let share = ((tile.shown as f64 - known) / undecided.len() as f64).clamp(0.0, 1.0);
+		for c in undecided {
+			let r = risk.entry(*c).or_insert(0.0);
+			*r = r.max(share);
+		}
+	}
+
+	let candidates: Vec<Coordintes> = (0..field.size())
+		.filter(|i| !field.field[*i].revealed)
+		.filter_map(|i| field.index_to_coordintes(i).ok())
+		.filter(|c| !mines.contains(c))
+		.collect();
+	let left = field.get_num_mines() as f64 - mines.len() as f64;
+	let density = (left / candidates.len().max(1) as f64).clamp(0.0, 1.0);
+
+	candidates.into_iter().min_by(|a, b| {
+		let a = risk.get(a).unwrap_or(&density);
+		let b = risk.get(b).unwrap_or(&density);
+		a.total_cmp(b)
+	})
+}
+
+// checks if every safe tile can be revealed from the start without guessing
+pub fn solvable(field: &Field, start: &Coordintes) -> bool {
+	let mut field = field.clone();
+	if field.reveal(start).is_err() || field.has_lost() {
+		return false;
+	}
+
+	solve(field)
+}
+
+// checks if every safe tile can be revealed from the tiles revealed so far without guessing
+pub fn solvable_from_here(field: &Field) -> bool {
+	solve(field.clone())
+}
+
+fn solve(field: Field) -> bool {
+	cleared(&solve_as_far(field))
+}
+
+fn cleared(field: &Field) -> bool {
+	field.field.iter().all(|t| t.revealed || t.is_mine)
+}
+
+// reveals safe tiles until the field is cleared or nothing more can be deduced, and returns the field at that point
+pub fn solve_as_far(mut field: Field) -> Field {
+	while !cleared(&field) {
+		let safe: Vec<Coordintes> = deduce(&field)
+			.into_iter()
+			.filter_map(|d| match d {
+				Deduction::Safe(c) => Some(c),
+				Deduction::Mine(_) => None,
+			})
+			.collect();
+		if safe.is_empty() {
+			break;
+		}
+
+		for c in safe {
+			if field.reveal(&c).is_err() {
+				return field;
+			}
+		}
+	}
+	field
+}
+
+#[cfg(test)]
+mod solver_tests {
+	use super::*;
+	use crate::{error::Error, field::rules::Rule};
+
+	#[test]
+	fn test_deductions_are_correct() -> Result<(), Error> {
+		for (rule, per_tile) in Rule::ALL.iter().flat_map(|r| [(*r, 1), (*r, 3)]) {
+			for seed in 0..20 {
+				let mut f = Field::new(16, 16, 40 * per_tile as u16);
+				f.set_mines_per_tile(per_tile);
+				f.set_rule(rule);
+				let start = Coordintes { x: 8, y: 8, z: 0 };
+				f.init(&start, seed)?;
+				f.reveal(&start)?;
+
+				// keep following hints until the solver is stuck, every deduction has to match the layout
+				loop {
+					let deductions = deduce(&f);
+					for d in &deductions {
+						match d {
+							Deduction::Safe(c) => {
+								if f.is_mine(c)? {
+									return Err(Error::new("mine deduced as safe"));
+								}
+							}
+							Deduction::Mine(c) => {
+								if !f.is_mine(c)? {
+									return Err(Error::new("safe tile deduced as mine"));
+								}
+							}
+						}
+					}
+
+					match hint(&f) {
+						None => break,
+						Some(c) => {
+							f.reveal(&c)?;
+						}
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
@@ -1,115 +1,78 @@
 use std::fmt::Display;
 
-use rand::{rngs::StdRng, Rng};
-
-#[derive(Debug, Clone)]
+// is_mine and flag are kept for the common case, mines and flags count them when tiles can hold more than one
+// value is the real number of mines around, shown is what the rule of the field displays instead
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Tile {
 	pub value: u8,
+	pub shown: i16,
 	pub is_mine: bool,
+	pub mines: u8,
+	// the mines count as -1 with the negative rule
+	pub negative: bool,
 	pub flag: bool,
+	pub flags: u8,
 	pub unknown: bool,
 	pub revealed: bool,
+	// player who found the mine in a versus game, counted from 0
+	pub owner: Option<u8>,
 }
 
 impl Tile {
 	pub fn new() -> Tile {
 		Tile {
 			value: 0,
+			shown: 0,
 			is_mine: false,
+			mines: 0,
+			negative: false,
 			flag: false,
+			flags: 0,
 			unknown: false,
 			revealed: false,
+			owner: None,
 		}
 	}
+
+	pub fn set_mines(&mut self, mines: u8) {
+		self.mines = mines;
+		self.is_mine = mines > 0;
+	}
+
+	pub fn set_flags(&mut self, flags: u8) {
+		self.flags = flags;
+		self.flag = flags > 0;
+	}
 }
 
+// z is the layer of three dimensional fields, it is always 0 on flat ones
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Coordintes {
 	pub x: u8,
 	pub y: u8,
+	pub z: u8,
 }
 
 impl Display for Coordintes {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "[{}, {}]", self.x, self.y)
+		if self.z == 0 {
+			write!(f, "[{}, {}]", self.x, self.y)
+		} else {
+			write!(f, "[{}, {}, {}]", self.x, self.y, self.z)
+		}
 	}
 }
 
 impl Coordintes {
 	pub fn new() -> Coordintes {
-		Coordintes { x: 0, y: 0 }
-	}
-
-	pub fn new_random(limit: &Coordintes, rng: &mut StdRng) -> Coordintes {
-		Coordintes {
-			x: rng.gen_range(0..limit.x),
-			y: rng.gen_range(0..limit.x),
-		}
+		Coordintes { x: 0, y: 0, z: 0 }
 	}
 
 	pub fn is_inside(&self, inside_of: &Coordintes) -> bool {
-		if self.x < inside_of.x && self.y < inside_of.y {
+		if self.x < inside_of.x && self.y < inside_of.y && self.z < inside_of.z {
 			return true;
 		}
 
 		false
 	}
-	pub fn get_surrounding(&self, limit: &Coordintes) -> Vec<Coordintes> {
-		let mut v = Vec::new();
-
-		if self.x > 0 {
-			if self.y > 0 {
-				v.push(Coordintes {
-					x: self.x - 1,
-					y: self.y - 1,
-				})
-			}
-
-			v.push(Coordintes {
-				x: self.x - 1,
-				y: self.y,
-			});
-
-			if self.y < limit.y - 1 {
-				v.push(Coordintes {
-					x: self.x - 1,
-					y: self.y + 1,
-				});
-			}
-		}
-
-		if self.x < limit.x - 1 {
-			if self.y > 0 {
-				v.push(Coordintes {
-					x: self.x + 1,
-					y: self.y - 1,
-				});
-			}
-
-			v.push(Coordintes {
-				x: self.x + 1,
-				y: self.y,
-			});
-
-			if self.y < limit.y - 1 {
-				v.push(Coordintes {
-					x: self.x + 1,
-					y: self.y + 1,
-				});
-			}
-		}
-		if self.y > 0 {
-			v.push(Coordintes {
-				x: self.x,
-				y: self.y - 1,
-			})
-		}
-		if self.y < limit.y - 1 {
-			v.push(Coordintes {
-				x: self.x,
-				y: self.y + 1,
-			})
-		}
-		v
-	}
 }
@@ -0,0 +1,241 @@
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::tile::Coordintes;
+use crate::{error::Error, fl};
+
+// decides which edges of the field are connected to each other
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum Topology {
+	// the classic field, tiles at the edges have fewer neighbours
+	#[default]
+	Rectangle,
+	// the left and right edges are connected
+	Cylinder,
+	// all edges are connected, every tile has 8 neighbours
+	Torus,
+}
+
+impl Topology {
+	pub fn wraps_x(&self) -> bool {
+		matches!(self, Topology::Cylinder | Topology::Torus)
+	}
+
+	pub fn wraps_y(&self) -> bool {
+		matches!(self, Topology::Torus)
+	}
+}
+
+// shape of a single tile
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum Grid {
+	// 8 neighbours, including the diagonals
+	#[default]
+	Square,
+	// 6 neighbours, odd rows are shifted right by half a tile
+	Hex,
+}
+
+// which tiles count as neighbours, the numbers show how many mines are among them
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Neighbourhood {
+	// every touching tile, 8 on square and 6 on hex grids
+	#[default]
+	Moore,
+	// only the 4 orthogonal tiles
+	VonNeumann,
+	// the tiles a knight in chess could jump to
+	Knight,
+	// every tile at most 2 steps away in both directions
+	Radius2,
+	// (x, y) offsets chosen by the player
+	Custom(Vec<(i8, i8)>),
+}
+
+const VON_NEUMANN: [(i16, i16); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const KNIGHT: [(i16, i16); 8] = [
+	(-1, -2),
+	(1, -2),
+	(-2, -1),
+	(2, -1),
+	(-2, 1),
+	(2, 1),
+	(-1, 2),
+	(1, 2),
+];
+
+impl Neighbourhood {
+	pub const PRESETS: [Neighbourhood; 4] = [
+		Neighbourhood::Moore,
+		Neighbourhood::VonNeumann,
+		Neighbourhood::Knight,
+		Neighbourhood::Radius2,
+	];
+}
+
+/*
+Accepts the name of a preset, or a list of offsets like "1,0;0,1;-1,0;0,-1".
+Offsets repeating or pointing at the tile itself are dropped
+*/
+impl FromStr for Neighbourhood {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"moore" => return Ok(Neighbourhood::Moore),
+			"von-neumann" => return Ok(Neighbourhood::VonNeumann),
+			"knight" => return Ok(Neighbourhood::Knight),
+			"radius-2" => return Ok(Neighbourhood::Radius2),
+			_ => (),
+		}
+
+		let invalid = || Error::new(&fl!("error-neighbourhood", value = s));
+		let mut offsets = Vec::new();
+		for pair in s.split(';') {
+			let (x, y) = pair.split_once(',').ok_or_else(invalid)?;
+			let x = x.trim().parse::<i8>().map_err(|_| invalid())?;
+			let y = y.trim().parse::<i8>().map_err(|_| invalid())?;
+			if (x, y) != (0, 0) && !offsets.contains(&(x, y)) {
+				offsets.push((x, y));
+			}
+		}
+
+		// how many neighbours the numbers can count depends on the whole field, see Field::check
+		if offsets.is_empty() {
+			return Err(invalid());
+		}
+
+		Ok(Neighbourhood::Custom(offsets))
+	}
+}
+
+const SQUARE: [(i16, i16); 8] = [
+	(-1, -1),
+	(0, -1),
+	(1, -1),
+	(-1, 0),
+	(1, 0),
+	(-1, 1),
+	(0, 1),
+	(1, 1),
+];
+
+// hex fields are stored with "odd-r" offset coordinates, so the neighbours depend on the row
+const HEX_EVEN_ROW: [(i16, i16); 6] = [(-1, -1), (0, -1), (-1, 0), (1, 0), (-1, 1), (0, 1)];
+const HEX_ODD_ROW: [(i16, i16); 6] = [(0, -1), (1, -1), (-1, 0), (1, 0), (0, 1), (1, 1)];
+
+/// Everything deciding which tiles are next to each other.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Geometry {
+	pub grid: Grid,
+	pub topology: Topology,
+	pub neighbourhood: Neighbourhood,
+}
+
+impl Geometry {
+	// offsets inside of the same layer
+	// rules other than Moore are applied to the stored coordinates, even on hex grids
+	fn offsets(&self, coords: &Coordintes) -> Vec<(i16, i16)> {
+		match (&self.neighbourhood, self.grid) {
+			(Neighbourhood::Moore, Grid::Square) => SQUARE.to_vec(),
+			(Neighbourhood::Moore, Grid::Hex) if coords.y % 2 == 1 => HEX_ODD_ROW.to_vec(),
+			(Neighbourhood::Moore, Grid::Hex) => HEX_EVEN_ROW.to_vec(),
+			(Neighbourhood::VonNeumann, _) => VON_NEUMANN.to_vec(),
+			(Neighbourhood::Knight, _) => KNIGHT.to_vec(),
+			(Neighbourhood::Radius2, _) => (-2..=2)
+				.flat_map(|y| (-2..=2).map(move |x| (x, y)))
+				.filter(|o| *o != (0, 0))
+				.collect(),
+			(Neighbourhood::Custom(offsets), _) => offsets
+				.iter()
+				.map(|(x, y)| (*x as i16, *y as i16))
+				.collect(),
+		}
+	}
+
+	// offsets in the layers directly above and below, von Neumann only reaches straight up and down
+	fn layer_offsets(&self, coords: &Coordintes) -> Vec<(i16, i16)> {
+		let mut offsets = vec![(0, 0)];
+		if self.neighbourhood != Neighbourhood::VonNeumann {
+			offsets.append(&mut self.offsets(coords));
+		}
+		offsets
+	}
+
+	// the most neighbours a tile can have, tiles at the edges or on small wrapping fields have fewer
+	pub fn max_neighbours(&self, limit: &Coordintes) -> usize {
+		let start = Coordintes::new();
+		let mut most = self.offsets(&start).len();
+		if limit.z > 1 {
+			most += 2 * self.layer_offsets(&start).len();
+		}
+		let others = (limit.x as usize * limit.y as usize * limit.z as usize).saturating_sub(1);
+		most.min(others)
+	}
+
+	/*
+	Hex rows alternate in their offset, so wrapping an odd number of rows would put two rows of the same offset
+	next to each other and tiles would see neighbours that do not see them back
+	*/
+	pub fn check(&self, limit: &Coordintes) -> Result<(), Error> {
+		if self.grid == Grid::Hex && self.topology.wraps_y() && limit.y % 2 == 1 {
+			return Err(Error::new(&fl!("error-hex-wrap", height = limit.y)));
+		}
+		Ok(())
+	}
+
+	/*
+	Returns every neighbour of the tile, on wrapping topologies neighbours can be on the opposite edge.
+	On fields with multiple layers the tiles directly above and below are neighbours as well,
+	together with their own neighbours in that layer, so a square tile has up to 26 neighbours.
+	Layers never wrap around
+	*/
+	pub fn neighbours(&self, coords: &Coordintes, limit: &Coordintes) -> Vec<Coordintes> {
+		let mut v = Vec::new();
+
+		for dz in -1..=1 {
+			let z = match offset(coords.z, dz, limit.z, false) {
+				None => continue,
+				Some(z) => z,
+			};
+
+			let offsets = if dz == 0 {
+				self.offsets(coords)
+			} else {
+				self.layer_offsets(coords)
+			};
+			for (dx, dy) in &offsets {
+				let x = match offset(coords.x, *dx, limit.x, self.topology.wraps_x()) {
+					None => continue,
+					Some(x) => x,
+				};
+				let y = match offset(coords.y, *dy, limit.y, self.topology.wraps_y()) {
+					None => continue,
+					Some(y) => y,
+				};
+
+				// on very small wrapping fields the same tile can be reached from multiple sides
+				let c = Coordintes { x, y, z };
+				if c != *coords && !v.contains(&c) {
+					v.push(c);
+				}
+			}
+		}
+
+		v
+	}
+}
+
+// moves a single axis by delta, returns None if that leaves the field
+fn offset(value: u8, delta: i16, limit: u8, wrap: bool) -> Option<u8> {
+	let moved = value as i16 + delta;
+	if wrap {
+		Some(moved.rem_euclid(limit as i16) as u8)
+	} else if moved < 0 || moved >= limit as i16 {
+		None
+	} else {
+		Some(moved as u8)
+	}
+}
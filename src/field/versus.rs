@@ -0,0 +1,132 @@
+use super::{tile::Coordintes, Field};
+use crate::{error::Error, fl};
+
+pub const PLAYERS: u8 = 2;
+
+// what a move in a versus game led to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Turn {
+	// a mine was found, the same player goes again
+	Found,
+	// a safe tile was revealed, it is the other player's turn
+	Passed,
+	// the tile was already revealed or the game is over, nothing changed
+	Ignored,
+}
+
+/*
+Minesweeper Flags, two players taking turns on the same field.
+Finding a mine scores a point for every mine in the tile and the player goes again, anything else ends the turn.
+Whoever found more than half of the mines wins. Which mines belong to whom is kept in the tiles,
+so only the player whose turn it is lives here
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Versus {
+	// counted from 0
+	pub player: u8,
+}
+
+impl Versus {
+	pub fn new() -> Versus {
+		Versus { player: 0 }
+	}
+
+	// the field has to be initialized already, the frontends place the mines on the first click like in other games
+	pub fn play(&mut self, field: &mut Field, coords: &Coordintes) -> Result<Turn, Error> {
+		if over(field) || field.already_revealed(coords)? {
+			return Ok(Turn::Ignored);
+		}
+
+		if field.claim(coords, self.player)? {
+			Ok(Turn::Found)
+		} else {
+			self.player = (self.player + 1) % PLAYERS;
+			Ok(Turn::Passed)
+		}
+	}
+
+	// whose turn it is and how the game stands, or how it ended
+	pub fn describe(&self, field: &Field) -> String {
+		if let Some(player) = winner(field) {
+			return fl!(
+				"versus-won",
+				player = (player + 1),
+				score = score(field, player)
+			);
+		}
+		if over(field) {
+			return fl!("versus-draw");
+		}
+		fl!(
+			"versus-turn",
+			player = (self.player + 1),
+			first = score(field, 0),
+			second = score(field, 1),
+			needed = needed(field)
+		)
+	}
+}
+
+// mines found by the player
+pub fn score(field: &Field, player: u8) -> u16 {
+	field
+		.get_field()
+		.iter()
+		.filter(|t| t.owner == Some(player))
+		.map(|t| t.mines as u16)
+		.sum()
+}
+
+// more than half of the mines
+pub fn needed(field: &Field) -> u16 {
+	field.get_num_mines() / 2 + 1
+}
+
+pub fn winner(field: &Field) -> Option<u8> {
+	(0..PLAYERS).find(|p| score(field, *p) >= needed(field))
+}
+
+// with an even number of mines both players can end up with half of them
+pub fn over(field: &Field) -> bool {
+	let found: u16 = (0..PLAYERS).map(|p| score(field, p)).sum();
+	winner(field).is_some() || found >= field.get_num_mines()
+}
+
+#[cfg(test)]
+mod versus_tests {
+	use super::*;
+
+	#[test]
+	fn test_turns() -> Result<(), Error> {
+		// three mines in the top row, so two of them win
+		let limit = Coordintes { x: 3, y: 3, z: 1 };
+		let mut field = Field::from_layout(limit, &[1, 1, 1, 0, 0, 0, 0, 0, 0])?;
+		let mut versus = Versus::new();
+		let at = |x, y| Coordintes { x, y, z: 0 };
+
+		// a safe tile passes the turn, a mine keeps it
+		if versus.play(&mut field, &at(0, 2))? != Turn::Passed || versus.player != 1 {
+			return Err(Error::new("safe tile kept the turn"));
+		}
+		if versus.play(&mut field, &at(0, 0))? != Turn::Found || versus.player != 1 {
+			return Err(Error::new("found mine passed the turn"));
+		}
+		if field.has_lost() || score(&field, 1) != 1 || score(&field, 0) != 0 {
+			return Err(Error::new("found mine scored wrongly"));
+		}
+
+		// revealed tiles cannot be played again
+		if versus.play(&mut field, &at(0, 0))? != Turn::Ignored || versus.player != 1 {
+			return Err(Error::new("revealed tile played again"));
+		}
+
+		if versus.play(&mut field, &at(1, 0))? != Turn::Found || winner(&field) != Some(1) {
+			return Err(Error::new("majority did not win"));
+		}
+		if !over(&field) || versus.play(&mut field, &at(2, 0))? != Turn::Ignored {
+			return Err(Error::new("game went on after it was won"));
+		}
+
+		Ok(())
+	}
+}
@@ -2,12 +2,15 @@ use cosmic::{cosmic_theme, iced::Alignment, theme, widget, Element};
 
 use crate::fl;
 
-use super::app::{Message, REPOSITORY};
+use super::{
+	app::{Message, REPOSITORY},
+	skin,
+};
 
 pub fn about() -> Element<'static, Message> {
 	let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
 
-	let icon = widget::icon::from_name("minesweeper");
+	let icon = widget::icon(skin::app_icon()).size(64);
 
 	let title = widget::text::title3(fl!("app-title"));
 
@@ -1,27 +1,79 @@
 use crate::args;
+use crate::config::{self, Config, Difficulty, Skin, Theme, CONFIG_VERSION};
+use crate::daily::{self, DailyStats, Date};
+use crate::error::Error;
 use crate::field::tile::Coordintes;
-use crate::field::Field;
-use crate::{args::Args, fl};
+use crate::field::{
+	code::BoardCode,
+	puzzle::Puzzle,
+	rules::Rule,
+	solver,
+	topology::{Grid, Neighbourhood},
+	versus::{self, Turn, Versus},
+	Field,
+};
+use crate::localization::describe_tile;
+use crate::race::Race;
+use crate::{
+	args::{Args, BoardArgs, PlayArgs},
+	fl,
+};
 
 use clap::Parser;
 use cosmic::app::{Command, Core};
-use cosmic::iced::window::Id;
-use cosmic::widget::icon::Handle;
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
+use cosmic::iced::keyboard::{Key, Modifiers};
+use cosmic::iced::mouse::{self, ScrollDelta};
+use cosmic::iced::window::{self, Id};
+use cosmic::iced::{event, keyboard, time, Event, Size, Subscription};
+use cosmic::widget::menu::action::MenuAction as _;
 use cosmic::widget::{self, menu};
 use cosmic::{Application, ApplicationExt, Element};
 use std::collections::HashMap;
+use std::time::Duration;
 
 use super::about::about;
-use super::main_view::get_field;
+use super::editor::{EditMode, Editor};
+use super::key_binds::{self, BoardAction};
+use super::main_view::{get_field, TILE_PADDING};
+use super::puzzles::PuzzlePage;
+use super::settings::{
+	SettingsPage, GRIDS, LAYERS, MINES_PER_TILE, SAFE_STARTS, TILE_SIZES, TOPOLOGIES,
+};
+use super::skin::Tiles;
 
 pub const REPOSITORY: &str = "https://github.com/Teddy-Kun/spacemines";
 
 /// This is the struct that represents your application.
 /// It is used to define the data that will be used by your application.
 pub struct Spacemines {
-	args: Args,
+	args: PlayArgs,
 	field: Field,
 	seed: u64,
+	/// Board code the current game was started from, new games with the same seed replay it.
+	code: Option<BoardCode>,
+	/// Text typed into the board code dialog.
+	code_input: String,
+	/// Date of the daily challenge being played, if it is one.
+	daily: Option<Date>,
+	/// Puzzle being played, new games with the same seed start it over.
+	puzzle: Option<Puzzle>,
+	/// Race played against others over the network, its board comes from the host.
+	race: Option<Race>,
+	/// Two player game on one screen, the players take turns revealing tiles.
+	versus: Option<Versus>,
+	/// Board being edited, replaces the game in the main view while open.
+	editor: Option<Editor>,
+	/// Tile focused for keyboard play.
+	cursor: Coordintes,
+	/// Previous states of the field, used to undo moves.
+	history: Vec<Field>,
+	/// Result of the last action in words, shown above the board for screen readers.
+	announcement: String,
+	/// Currently held modifiers, needed to tell zooming apart from scrolling.
+	modifiers: Modifiers,
+	/// Last known size of the main window, used to fit the board into it.
+	window_size: Size,
 
 	/// Application state which is managed by the COSMIC runtime.
 	core: Core,
@@ -30,7 +82,17 @@ pub struct Spacemines {
 	/// Key bindings for the application's menu bar.
 	key_binds: HashMap<menu::KeyBind, MenuAction>,
 
-	mine_icon: Handle,
+	/// Persistent settings, kept in sync with the config file.
+	config: Config,
+	/// Handle used to write the settings back, `None` if the config could not be opened.
+	config_handler: Option<cosmic_config::Config>,
+	settings_page: SettingsPage,
+	puzzle_page: PuzzlePage,
+	/// Confirmation dialog currently shown, if any.
+	dialog: Option<DialogPage>,
+
+	/// Icons of the selected skin.
+	tiles: Tiles,
 }
 
 /// This is the enum that contains all the possible variants that your application will need to transmit messages.
@@ -40,10 +102,55 @@ pub struct Spacemines {
 pub enum Message {
 	NewGame,
 	NewSeed,
+	PasteCode,
+	CodeInput(String),
+	Daily,
+	Puzzles,
+	PlayPuzzle(usize),
+	Versus,
+	Editor,
+	EditTile(Coordintes),
+	SetEditMode(usize),
+	SetEditWidth(usize),
+	SetEditHeight(usize),
+	EditName(String),
+	EditFile(String),
+	CheckEdit,
+	SaveEdit,
+	PlayEdit,
+	CloseEditor,
+	RaceTick,
 	Click(Coordintes),
 	RClick(Coordintes),
+	Chord(Coordintes),
+	Undo,
+	Hint,
+	Key(Modifiers, Key),
+	ModifiersChanged(Modifiers),
+	Scroll(ScrollDelta),
+	WindowResized(Size),
+	ZoomIn,
+	ZoomOut,
+	FitWindow,
 	LaunchUrl(String),
 	ToggleContextPage(ContextPage),
+	UpdateConfig(Config),
+	SetDifficulty(usize),
+	SetSafeStart(usize),
+	SetTopology(usize),
+	SetGrid(usize),
+	SetLayers(usize),
+	SetNeighbourhood(usize),
+	SetMinesPerTile(usize),
+	SetRule(usize),
+	SetDailySalt(String),
+	SetQuestionMarks(bool),
+	SetConfirmPrompts(bool),
+	SetTheme(usize),
+	SetSkin(usize),
+	SetTileSize(usize),
+	DialogConfirm,
+	DialogCancel,
 }
 
 /// Identifies a context page to display in the context drawer.
@@ -51,21 +158,49 @@ pub enum Message {
 pub enum ContextPage {
 	#[default]
 	About,
+	Settings,
+	Puzzles,
+	Editor,
 }
 
 impl ContextPage {
 	fn title(&self) -> String {
 		match self {
 			Self::About => fl!("about"),
+			Self::Settings => fl!("settings"),
+			Self::Puzzles => fl!("puzzles"),
+			Self::Editor => fl!("editor"),
 		}
 	}
 }
 
+/// Identifies an action that has to be confirmed before it is executed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DialogPage {
+	NewGame { new_seed: bool },
+	Daily,
+	Puzzle { index: usize },
+	PlayEdit,
+	Versus,
+	BoardCode { invalid: bool },
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum MenuAction {
 	NewGame,
 	NewSeed,
+	PasteCode,
+	Daily,
+	Puzzles,
+	Versus,
+	Editor,
+	Undo,
+	Hint,
+	ZoomIn,
+	ZoomOut,
+	FitWindow,
 	About,
+	Settings,
 }
 
 impl menu::action::MenuAction for MenuAction {
@@ -75,7 +210,18 @@ impl menu::action::MenuAction for MenuAction {
 		match self {
 			MenuAction::NewGame => Message::NewGame,
 			MenuAction::NewSeed => Message::NewSeed,
+			MenuAction::PasteCode => Message::PasteCode,
+			MenuAction::Daily => Message::Daily,
+			MenuAction::Puzzles => Message::Puzzles,
+			MenuAction::Versus => Message::Versus,
+			MenuAction::Editor => Message::Editor,
+			MenuAction::Undo => Message::Undo,
+			MenuAction::Hint => Message::Hint,
+			MenuAction::ZoomIn => Message::ZoomIn,
+			MenuAction::ZoomOut => Message::ZoomOut,
+			MenuAction::FitWindow => Message::FitWindow,
 			MenuAction::About => Message::ToggleContextPage(ContextPage::About),
+			MenuAction::Settings => Message::ToggleContextPage(ContextPage::Settings),
 		}
 	}
 }
@@ -95,7 +241,7 @@ impl Application for Spacemines {
 
 	type Message = Message;
 
-	const APP_ID: &'static str = "de.teddy-kun.Spacemines";
+	const APP_ID: &'static str = config::APP_ID;
 
 	fn core(&self) -> &Core {
 		&self.core
@@ -113,22 +259,89 @@ impl Application for Spacemines {
 	/// - `flags` is used to pass in any data that your application needs to use before it starts.
 	/// - `Command` type is used to send messages to your application. `Command::none()` can be used to send no messages to your application.
 	fn init(core: Core, _flags: Self::Flags) -> (Self, Command<Self::Message>) {
-		let args = Args::parse();
-		let width = args.width;
-		let height = args.height;
-		let mines = args.mines;
-		let seed = args.get_seed();
+		let args = Args::parse().play().clone();
+		let seed = args.board.get_seed();
+
+		let config_handler = cosmic_config::Config::new(Self::APP_ID, CONFIG_VERSION).ok();
+		let config = match &config_handler {
+			None => Config::default(),
+			Some(handler) => match Config::get_entry(handler) {
+				Ok(config) => config,
+				// missing or broken keys are replaced with their defaults
+				Err((_errors, config)) => config,
+			},
+		};
+
+		let daily = args.board.daily();
+		let code = match daily {
+			None => args.board.code.clone(),
+			Some(date) => Some(daily::board(date, &args.board.daily_salt(&config))),
+		};
+		let field = match code
+			.as_ref()
+			.map(BoardCode::play)
+			.or_else(|| args.board.import())
+		{
+			Some(Ok(field)) => field,
+			Some(Err(e)) => {
+				e.out();
+				new_field(&args.board, &config)
+			}
+			None => new_field(&args.board, &config),
+		};
+		args.export(&field);
+		let race = match Race::from_args(&args, &config) {
+			Ok(r) => r,
+			Err(e) => {
+				e.out();
+				None
+			}
+		};
+		let versus = args.versus.then(Versus::new);
+		let tiles = Tiles::new(config.skin);
+		let open_puzzles = args.puzzles.is_some();
+		let args_puzzle_dir = args.puzzle_dir();
 		let mut app = Spacemines {
 			args,
-			field: Field::new(width, height, mines),
+			field,
 			seed,
+			code,
+			code_input: String::new(),
+			daily,
+			puzzle: None,
+			race,
+			versus,
+			editor: None,
+			cursor: Coordintes::new(),
+			history: Vec::new(),
+			announcement: String::new(),
+			modifiers: Modifiers::empty(),
+			window_size: Size::ZERO,
 			core,
 			context_page: ContextPage::default(),
-			key_binds: HashMap::new(),
-			mine_icon: widget::icon::from_name("name").size(16).handle(),
+			key_binds: key_binds::key_binds(),
+			config,
+			config_handler,
+			settings_page: SettingsPage::new(),
+			puzzle_page: PuzzlePage::new(args_puzzle_dir),
+			dialog: None,
+			tiles,
 		};
 
-		let command = app.update_titles();
+		if let Some(date) = daily {
+			app.announcement = fl!("daily-title", date = date.to_string());
+		}
+		if open_puzzles {
+			app.puzzle_page.reload();
+			app.context_page = ContextPage::Puzzles;
+			app.core.window.show_context = true;
+			app.set_context_title(ContextPage::Puzzles.title());
+		}
+
+		let command = Command::batch(vec![
+			app.update_titles(),
+			cosmic::app::command::set_theme(app.config.theme.theme()),
+		]);
 
 		(app, command)
 	}
@@ -142,6 +355,14 @@ impl Application for Spacemines {
 				vec![
 					menu::Item::Button(fl!("new-game"), MenuAction::NewGame),
 					menu::Item::Button(fl!("seed"), MenuAction::NewSeed),
+					menu::Item::Button(fl!("paste-board-code"), MenuAction::PasteCode),
+					menu::Item::Button(fl!("daily-challenge"), MenuAction::Daily),
+					menu::Item::Button(fl!("puzzles"), MenuAction::Puzzles),
+					menu::Item::Button(fl!("versus"), MenuAction::Versus),
+					menu::Item::Button(fl!("editor"), MenuAction::Editor),
+					menu::Item::Divider,
+					menu::Item::Button(fl!("undo"), MenuAction::Undo),
+					menu::Item::Button(fl!("hint"), MenuAction::Hint),
 				],
 			),
 		);
@@ -150,7 +371,14 @@ impl Application for Spacemines {
 			menu::root(fl!("view")),
 			menu::items(
 				&self.key_binds,
-				vec![menu::Item::Button(fl!("about"), MenuAction::About)],
+				vec![
+					menu::Item::Button(fl!("zoom-in"), MenuAction::ZoomIn),
+					menu::Item::Button(fl!("zoom-out"), MenuAction::ZoomOut),
+					menu::Item::Button(fl!("fit-window"), MenuAction::FitWindow),
+					menu::Item::Divider,
+					menu::Item::Button(fl!("settings"), MenuAction::Settings),
+					menu::Item::Button(fl!("about"), MenuAction::About),
+				],
 			),
 		);
 		let menu_bar = menu::bar(vec![options, view]);
@@ -165,29 +393,221 @@ impl Application for Spacemines {
 	///
 	/// To get a better sense of which widgets are available, check out the `widget` module.
 	fn view(&self) -> Element<Self::Message> {
-		get_field(&self.field, self.mine_icon.clone())
+		if let Some(editor) = &self.editor {
+			return editor.board_view(self.tiles.current(), self.config.tile_size);
+		}
+
+		let mut status = match &self.race {
+			Some(race) if race.started() => format!("{}\n{}", self.announcement, race.describe()),
+			_ => self.announcement.clone(),
+		};
+		if let Some(versus) = &self.versus {
+			status = format!("{} {}", versus.describe(&self.field), status);
+		}
+		get_field(
+			&self.field,
+			self.tiles.current(),
+			self.config.tile_size,
+			self.cursor,
+			&status,
+		)
 	}
 
 	/// Application messages are handled here. The application state can be modified based on
 	/// what message was received. Commands may be returned for asynchronous execution on a
 	/// background thread managed by the application's executor.
 	fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+		// everyone in a race plays the same board without help
+		if self.race.is_some()
+			&& matches!(
+				message,
+				Message::PasteCode
+					| Message::Daily
+					| Message::PlayPuzzle(_)
+					| Message::PlayEdit
+					| Message::Versus
+					| Message::Undo | Message::Hint
+			) {
+			return Command::none();
+		}
+		// and players taking turns get no help either, chords could reveal mines for them
+		if self.versus.is_some()
+			&& matches!(
+				message,
+				Message::Daily | Message::Undo | Message::Hint | Message::Chord(_)
+			) {
+			return Command::none();
+		}
+
 		match message {
 			Message::NewGame => {
-				self.field = Field::new(self.args.width, self.args.height, self.args.mines);
+				self.request_new_game(false);
 			}
 
 			Message::NewSeed => {
-				self.seed = args::new_random_seed();
-				self.field = Field::new(self.args.width, self.args.height, self.args.mines);
+				self.request_new_game(true);
+			}
+
+			Message::PasteCode => {
+				self.code_input = String::new();
+				self.dialog = Some(DialogPage::BoardCode { invalid: false });
+			}
+
+			Message::CodeInput(text) => {
+				self.code_input = text;
+			}
+
+			Message::Daily => {
+				if self.config.confirm_prompts && self.running() {
+					self.dialog = Some(DialogPage::Daily);
+				} else {
+					self.play_daily();
+				}
+			}
+
+			Message::Puzzles => {
+				self.puzzle_page.reload();
+				return self.update(Message::ToggleContextPage(ContextPage::Puzzles));
+			}
+
+			Message::PlayPuzzle(index) => {
+				if self.config.confirm_prompts && self.running() {
+					self.dialog = Some(DialogPage::Puzzle { index });
+				} else {
+					self.play_puzzle(index);
+				}
+			}
+
+			Message::Versus => {
+				if self.config.confirm_prompts && self.running() {
+					self.dialog = Some(DialogPage::Versus);
+				} else {
+					self.toggle_versus();
+				}
+			}
+
+			Message::Editor => {
+				if self.editor.is_none() {
+					self.editor = Some(Editor::new(&self.field));
+				}
+				self.context_page = ContextPage::Editor;
+				self.core.window.show_context = true;
+				self.set_context_title(ContextPage::Editor.title());
+			}
+
+			Message::EditTile(coords) => {
+				if let Some(editor) = &mut self.editor {
+					editor.toggle(coords);
+				}
+			}
+
+			Message::SetEditMode(i) => {
+				if let (Some(editor), Some(mode)) = (&mut self.editor, EditMode::ALL.get(i)) {
+					editor.set_mode(*mode);
+				}
+			}
+
+			Message::SetEditWidth(i) => {
+				if let Some(editor) = &mut self.editor {
+					editor.width(i);
+				}
+			}
+
+			Message::SetEditHeight(i) => {
+				if let Some(editor) = &mut self.editor {
+					editor.height(i);
+				}
+			}
+
+			Message::EditName(name) => {
+				if let Some(editor) = &mut self.editor {
+					editor.set_name(name);
+				}
+			}
+
+			Message::EditFile(file) => {
+				if let Some(editor) = &mut self.editor {
+					editor.set_file(file);
+				}
+			}
+
+			Message::CheckEdit => {
+				if let Some(editor) = &mut self.editor {
+					editor.check();
+				}
+			}
+
+			Message::SaveEdit => {
+				if let Some(editor) = &mut self.editor {
+					editor.save();
+				}
+			}
+
+			Message::PlayEdit => {
+				if self.config.confirm_prompts && self.running() {
+					self.dialog = Some(DialogPage::PlayEdit);
+				} else {
+					self.play_edit();
+				}
+			}
+
+			Message::CloseEditor => {
+				self.editor = None;
+				self.core.window.show_context = false;
+			}
+
+			Message::RaceTick => {
+				let (waiting, code) = match &mut self.race {
+					Some(race) => {
+						let waiting = !race.started();
+						if !race.poll() {
+							return Command::none();
+						}
+						if waiting && !race.started() {
+							self.announcement = race.waiting();
+						}
+						(waiting, race.code.clone())
+					}
+					None => return Command::none(),
+				};
+				if let (true, Some(code)) = (waiting, code) {
+					self.daily = None;
+					self.play_code(code);
+					self.announcement = fl!("race-started");
+				}
 			}
 
 			Message::Click(coords) => {
+				if self.game_over() || self.race_waiting() {
+					return Command::none();
+				}
+
 				println!("Clicked {}", coords);
+				self.cursor = coords;
+				self.history.push(self.field.clone());
 				if !self.field.is_initialized() {
 					if let Err(e) = self.field.init(&coords, self.seed) {
-						e.fatal();
+						self.history.pop();
+						self.announcement = e.message().to_string();
+						return Command::none();
+					}
+					self.args.export(&self.field);
+				}
+
+				let turn = match &mut self.versus {
+					Some(versus) => Some(versus.play(&mut self.field, &coords)),
+					None => None,
+				};
+				if let Some(turn) = turn {
+					match turn {
+						Err(e) => e.fatal(),
+						Ok(Turn::Found) => {
+							let player = self.versus.map(|v| v.player).unwrap_or_default();
+							self.announcement = fl!("versus-found", player = (player + 1));
+						}
+						Ok(_) => self.announce_tile(coords),
 					}
+					return Command::none();
 				}
 
 				let revealed = match self.field.already_revealed(&coords) {
@@ -206,12 +626,128 @@ impl Application for Spacemines {
 
 					println!("{}", self.field);
 				}
+				self.announce_tile(coords);
+				self.finish_daily();
+				self.report_race();
 			}
 
 			Message::RClick(coords) => {
-				if let Err(e) = self.field.toggle_mark(&coords) {
+				if self.game_over() || self.race_waiting() {
+					return Command::none();
+				}
+
+				self.cursor = coords;
+				self.history.push(self.field.clone());
+				let result = if self.config.question_marks {
+					self.field.toggle_mark(&coords)
+				} else {
+					self.field.flag(&coords)
+				};
+
+				if let Err(e) = result {
 					e.fatal()
 				}
+				self.announce_tile(coords);
+				self.finish_daily();
+				self.report_race();
+			}
+
+			Message::Chord(coords) => {
+				if self.game_over() || self.race_waiting() {
+					return Command::none();
+				}
+
+				self.cursor = coords;
+				self.history.push(self.field.clone());
+				if let Err(e) = self.field.chord(&coords) {
+					e.fatal()
+				}
+				self.announce_tile(coords);
+				self.finish_daily();
+				self.report_race();
+			}
+
+			Message::Undo => {
+				if let Some(field) = self.history.pop() {
+					self.field = field;
+					self.announce_tile(self.cursor);
+				}
+			}
+
+			Message::Hint => {
+				self.announcement = match solver::hint(&self.field) {
+					Some(coords) => {
+						self.cursor = coords;
+						let hint = fl!("hint-safe", row = (coords.y + 1), column = (coords.x + 1));
+						self.in_layer(coords, hint)
+					}
+					None => fl!("hint-none"),
+				};
+			}
+
+			Message::Key(modifiers, key) => {
+				for (key_bind, action) in self.key_binds.iter() {
+					if key_bind.matches(modifiers, &key) {
+						return self.update(action.message());
+					}
+				}
+
+				if !modifiers.is_empty() || self.dialog.is_some() || self.editor.is_some() {
+					return Command::none();
+				}
+
+				if let Some(action) = key_binds::board_action(&key) {
+					let message = match action {
+						BoardAction::Move(dx, dy) => {
+							self.move_cursor(dx, dy);
+							return Command::none();
+						}
+						BoardAction::Layer(dz) => {
+							self.change_layer(dz);
+							return Command::none();
+						}
+						BoardAction::Reveal => Message::Click(self.cursor),
+						BoardAction::Flag => Message::RClick(self.cursor),
+						BoardAction::Chord => Message::Chord(self.cursor),
+					};
+					return self.update(message);
+				}
+			}
+
+			Message::ModifiersChanged(modifiers) => {
+				self.modifiers = modifiers;
+			}
+
+			Message::Scroll(delta) => {
+				// plain scrolling is handled by the scrollable around the board
+				if self.modifiers.control() {
+					let y = match delta {
+						ScrollDelta::Lines { y, .. } => y,
+						ScrollDelta::Pixels { y, .. } => y,
+					};
+
+					if y > 0.0 {
+						self.zoom(1);
+					} else if y < 0.0 {
+						self.zoom(-1);
+					}
+				}
+			}
+
+			Message::WindowResized(size) => {
+				self.window_size = size;
+			}
+
+			Message::ZoomIn => {
+				self.zoom(1);
+			}
+
+			Message::ZoomOut => {
+				self.zoom(-1);
+			}
+
+			Message::FitWindow => {
+				self.fit_window();
 			}
 
 			Message::LaunchUrl(url) => {
@@ -231,6 +767,142 @@ impl Application for Spacemines {
 				// Set the title of the context drawer.
 				self.set_context_title(context_page.title());
 			}
+
+			Message::UpdateConfig(config) => {
+				// the config file was changed, either by us or externally
+				let theme_changed = config.theme != self.config.theme;
+				if config.skin != self.config.skin {
+					self.tiles = Tiles::new(config.skin);
+				}
+				self.config = config;
+				if theme_changed {
+					return cosmic::app::command::set_theme(self.config.theme.theme());
+				}
+			}
+
+			Message::SetDifficulty(i) => {
+				if let Some(difficulty) = Difficulty::ALL.get(i) {
+					self.config.difficulty = *difficulty;
+					self.save_config();
+				}
+			}
+
+			Message::SetSafeStart(i) => {
+				if let Some(safe_start) = SAFE_STARTS.get(i) {
+					self.config.safe_start = *safe_start;
+					self.save_config();
+				}
+			}
+
+			Message::SetTopology(i) => {
+				if let Some(topology) = TOPOLOGIES.get(i) {
+					self.config.topology = *topology;
+					self.save_config();
+				}
+			}
+
+			Message::SetGrid(i) => {
+				if let Some(grid) = GRIDS.get(i) {
+					self.config.grid = *grid;
+					self.save_config();
+				}
+			}
+
+			Message::SetLayers(i) => {
+				if let Some(layers) = LAYERS.get(i) {
+					self.config.layers = *layers;
+					self.save_config();
+				}
+			}
+
+			Message::SetNeighbourhood(i) => {
+				if let Some(neighbourhood) = Neighbourhood::PRESETS.get(i) {
+					self.config.neighbourhood = neighbourhood.clone();
+					self.save_config();
+				}
+			}
+
+			Message::SetMinesPerTile(i) => {
+				if let Some(mines) = MINES_PER_TILE.get(i) {
+					self.config.mines_per_tile = *mines;
+					self.save_config();
+				}
+			}
+
+			Message::SetRule(i) => {
+				if let Some(rule) = Rule::ALL.get(i) {
+					self.config.rule = *rule;
+					self.save_config();
+				}
+			}
+
+			Message::SetDailySalt(salt) => {
+				self.config.daily_salt = salt;
+				self.save_config();
+			}
+
+			Message::SetQuestionMarks(enabled) => {
+				self.config.question_marks = enabled;
+				self.save_config();
+			}
+
+			Message::SetConfirmPrompts(enabled) => {
+				self.config.confirm_prompts = enabled;
+				self.save_config();
+			}
+
+			Message::SetTheme(i) => {
+				if let Some(theme) = Theme::ALL.get(i) {
+					self.config.theme = *theme;
+					self.save_config();
+					return cosmic::app::command::set_theme(self.config.theme.theme());
+				}
+			}
+
+			Message::SetSkin(i) => {
+				if let Some(skin) = Skin::ALL.get(i) {
+					self.config.skin = *skin;
+					self.tiles = Tiles::new(*skin);
+					self.save_config();
+				}
+			}
+
+			Message::SetTileSize(i) => {
+				if let Some(size) = TILE_SIZES.get(i) {
+					self.config.tile_size = *size;
+					self.save_config();
+				}
+			}
+
+			Message::DialogConfirm => match self.dialog.take() {
+				Some(DialogPage::NewGame { new_seed }) => {
+					self.new_game(new_seed);
+				}
+				Some(DialogPage::Daily) => {
+					self.play_daily();
+				}
+				Some(DialogPage::Puzzle { index }) => {
+					self.play_puzzle(index);
+				}
+				Some(DialogPage::PlayEdit) => {
+					self.play_edit();
+				}
+				Some(DialogPage::Versus) => {
+					self.toggle_versus();
+				}
+				Some(DialogPage::BoardCode { .. }) => match self.code_input.parse::<BoardCode>() {
+					Ok(code) => {
+						self.daily = None;
+						self.play_code(code);
+					}
+					Err(_) => self.dialog = Some(DialogPage::BoardCode { invalid: true }),
+				},
+				None => {}
+			},
+
+			Message::DialogCancel => {
+				self.dialog = None;
+			}
 		}
 		Command::none()
 	}
@@ -243,8 +915,89 @@ impl Application for Spacemines {
 
 		Some(match self.context_page {
 			ContextPage::About => self.about(),
+			ContextPage::Settings => self.settings_page.view(&self.config),
+			ContextPage::Puzzles => self.puzzle_page.view(),
+			ContextPage::Editor => match &self.editor {
+				Some(editor) => editor.page(),
+				None => widget::text(String::new()).into(),
+			},
 		})
 	}
+
+	/// Display a dialog asking for confirmation if one is pending.
+	fn dialog(&self) -> Option<Element<Self::Message>> {
+		let dialog = match self.dialog? {
+			DialogPage::NewGame { .. }
+			| DialogPage::Daily
+			| DialogPage::Puzzle { .. }
+			| DialogPage::PlayEdit
+			| DialogPage::Versus => widget::dialog(fl!("new-game-confirm-title"))
+				.body(fl!("new-game-confirm-body"))
+				.primary_action(
+					widget::button::suggested(fl!("new-game")).on_press(Message::DialogConfirm),
+				)
+				.secondary_action(
+					widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+				),
+			DialogPage::BoardCode { invalid } => widget::dialog(fl!("paste-board-code"))
+				.body(if invalid {
+					fl!("board-code-invalid")
+				} else {
+					fl!("paste-board-code-body")
+				})
+				.control(
+					widget::text_input(fl!("board-code-placeholder"), &self.code_input)
+						.on_input(Message::CodeInput)
+						.on_submit(Message::DialogConfirm),
+				)
+				.primary_action(
+					widget::button::suggested(fl!("board-code-play"))
+						.on_press(Message::DialogConfirm),
+				)
+				.secondary_action(
+					widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+				),
+		};
+
+		Some(dialog.into())
+	}
+
+	/// Watches the config file, so changes made outside of the app are applied immediately,
+	/// and listens for key presses not consumed by a widget.
+	fn subscription(&self) -> Subscription<Self::Message> {
+		struct ConfigSubscription;
+
+		let mut subscriptions = vec![
+			cosmic_config::config_subscription(
+				std::any::TypeId::of::<ConfigSubscription>(),
+				Self::APP_ID.into(),
+				CONFIG_VERSION,
+			)
+			.map(|update: cosmic_config::Update<Config>| Message::UpdateConfig(update.config)),
+			event::listen_with(|event, status| match event {
+				Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => match status
+				{
+					event::Status::Ignored => Some(Message::Key(modifiers, key)),
+					event::Status::Captured => None,
+				},
+				Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+					Some(Message::ModifiersChanged(modifiers))
+				}
+				Event::Mouse(mouse::Event::WheelScrolled { delta }) => Some(Message::Scroll(delta)),
+				Event::Window(_, window::Event::Resized { width, height }) => Some(
+					Message::WindowResized(Size::new(width as f32, height as f32)),
+				),
+				_ => None,
+			}),
+		];
+
+		// the host is asked for news a few times a second while racing
+		if self.race.is_some() {
+			subscriptions.push(time::every(Duration::from_millis(250)).map(|_| Message::RaceTick));
+		}
+
+		Subscription::batch(subscriptions)
+	}
 }
 
 impl Spacemines {
@@ -261,4 +1014,293 @@ impl Spacemines {
 		self.set_header_title(header_title);
 		self.set_window_title(window_title, Id::unique())
 	}
+
+	/// Starts a new game, asking first if that would throw away a running one.
+	fn request_new_game(&mut self, new_seed: bool) {
+		if self.config.confirm_prompts && self.running() {
+			self.dialog = Some(DialogPage::NewGame { new_seed });
+		} else {
+			self.new_game(new_seed);
+		}
+	}
+
+	fn running(&self) -> bool {
+		self.field.is_initialized() && !self.game_over()
+	}
+
+	fn new_game(&mut self, new_seed: bool) {
+		// races start over on the same board
+		if new_seed && self.race.is_none() {
+			self.seed = args::new_random_seed();
+			self.code = None;
+			self.daily = None;
+			self.puzzle = None;
+		}
+		if let Some(puzzle) = self.puzzle.clone() {
+			self.start_puzzle(puzzle);
+			return;
+		}
+		if let Some(code) = self.code.clone() {
+			self.play_code(code);
+			return;
+		}
+		// settings that cannot make a board keep the current one, the error explains what to change
+		match self.args.board.new_field(&self.config) {
+			Ok(field) => self.field = field,
+			Err(e) => {
+				self.announcement = e.message().to_string();
+				return;
+			}
+		}
+		self.versus = self.versus.map(|_| Versus::new());
+		self.history.clear();
+		self.cursor = Coordintes::new();
+		self.announcement = String::new();
+	}
+
+	/// Replaces the current game with the board of a shared code, the first click is already made.
+	fn play_code(&mut self, code: BoardCode) {
+		match code.play() {
+			Err(e) => {
+				self.announcement = e.message().to_string();
+				return;
+			}
+			Ok(field) => self.field = field,
+		}
+		self.versus = self.versus.map(|_| Versus::new());
+		self.seed = code.seed;
+		self.puzzle = None;
+		self.cursor = code.start;
+		self.code = Some(code);
+		self.history.clear();
+		self.announce_tile(self.cursor);
+		self.report_race();
+	}
+
+	/// Switches between games of two players taking turns and games of one, starting a new game.
+	fn toggle_versus(&mut self) {
+		self.versus = match self.versus {
+			Some(_) => None,
+			None => Some(Versus::new()),
+		};
+		self.new_game(false);
+	}
+
+	/// Whether a race was joined that has not started yet, the board cannot be played until then.
+	fn race_waiting(&self) -> bool {
+		self.race.as_ref().is_some_and(|r| !r.started())
+	}
+
+	/// Tells the host of a race how far the current board is cleared.
+	fn report_race(&mut self) {
+		if let Some(race) = &mut self.race {
+			if race.started() {
+				race.report(&self.field);
+			}
+		}
+	}
+
+	/// Plays a puzzle from the browser, the puzzle already has its revealed tiles open.
+	fn play_puzzle(&mut self, index: usize) {
+		if let Some(puzzle) = self.puzzle_page.get(index).cloned() {
+			self.code = None;
+			self.daily = None;
+			self.start_puzzle(puzzle);
+		}
+	}
+
+	/// Leaves the editor and plays the board being edited as a puzzle.
+	fn play_edit(&mut self) {
+		if let Some(editor) = self.editor.take() {
+			self.code = None;
+			self.daily = None;
+			self.core.window.show_context = false;
+			self.start_puzzle(editor.puzzle().clone());
+		}
+	}
+
+	fn start_puzzle(&mut self, puzzle: Puzzle) {
+		self.field = puzzle.field.clone();
+		self.versus = self.versus.map(|_| Versus::new());
+		self.history.clear();
+		self.cursor = Coordintes::new();
+		self.announcement = fl!("puzzle-title", name = puzzle.name.clone());
+		self.puzzle = Some(puzzle);
+	}
+
+	/// Starts the daily challenge of today, which is the same board for everyone using the same salt.
+	fn play_daily(&mut self) {
+		let date = Date::today();
+		self.play_code(daily::board(
+			date,
+			&self.args.board.daily_salt(&self.config),
+		));
+		self.daily = Some(date);
+		self.announcement = format!(
+			"{} {}",
+			fl!("daily-title", date = date.to_string()),
+			self.announcement
+		);
+	}
+
+	/// Records the result once a daily challenge is over, only challenges of today count.
+	fn finish_daily(&mut self) {
+		let date = match self.daily {
+			// versus games are not played alone, so they do not count
+			Some(d) if self.game_over() && self.versus.is_none() => d,
+			_ => return,
+		};
+
+		let today = Date::today();
+		let mut stats = DailyStats::load();
+		if date == today && stats.record(date, !self.field.has_lost()) {
+			if let Err(e) = stats.save() {
+				e.out();
+			}
+		}
+		self.announcement = format!("{} {}", self.announcement, stats.summary(today));
+	}
+
+	/// Steps through the tile size presets, saving the new size.
+	fn zoom(&mut self, steps: i32) {
+		// sizes that are not a preset, e.g. from editing the config by hand, snap to the closest one
+		let current = TILE_SIZES
+			.iter()
+			.position(|s| *s >= self.config.tile_size)
+			.unwrap_or(TILE_SIZES.len() - 1) as i32;
+		let index = (current + steps).clamp(0, TILE_SIZES.len() as i32 - 1) as usize;
+
+		if TILE_SIZES[index] != self.config.tile_size {
+			self.config.tile_size = TILE_SIZES[index];
+			self.save_config();
+		}
+	}
+
+	/// Picks the largest tile size preset at which the whole board fits into the window.
+	fn fit_window(&mut self) {
+		// space taken up by the header bar and the status line
+		const RESERVED_HEIGHT: f32 = 96.0;
+		const RESERVED_WIDTH: f32 = 16.0;
+
+		let limit = self.field.get_limit();
+		if limit.x == 0 || limit.y == 0 || self.window_size == Size::ZERO {
+			return;
+		}
+
+		// shifted hex rows stick out by half a tile
+		let columns = match self.field.get_geometry().grid {
+			Grid::Square => limit.x as f32,
+			Grid::Hex => limit.x as f32 + 0.5,
+		};
+		// layers are shown next to each other
+		let columns = columns * limit.z as f32;
+		let width = (self.window_size.width - RESERVED_WIDTH) / columns;
+		let height = (self.window_size.height - RESERVED_HEIGHT) / limit.y as f32;
+		let available = width.min(height) - 2.0 * TILE_PADDING as f32;
+
+		let size = TILE_SIZES
+			.iter()
+			.rev()
+			.find(|s| **s as f32 <= available)
+			.unwrap_or(&TILE_SIZES[0]);
+
+		if *size != self.config.tile_size {
+			self.config.tile_size = *size;
+			self.save_config();
+		}
+	}
+
+	fn game_over(&self) -> bool {
+		// flags do not win versus games, only found mines do
+		if self.versus.is_some() {
+			return versus::over(&self.field);
+		}
+		self.field.has_lost() || (self.field.is_initialized() && self.field.victory())
+	}
+
+	/// Describes the given tile in the status line, so screen readers announce the result of a move.
+	fn announce_tile(&mut self, coords: Coordintes) {
+		let state = match self.field.get_tile(&coords) {
+			Err(_) => return,
+			Ok(tile) => describe_tile(tile, self.field.has_lost()),
+		};
+		let announcement = fl!(
+			"announce-tile",
+			row = (coords.y + 1),
+			column = (coords.x + 1),
+			state = state
+		);
+		self.announcement = self.in_layer(coords, announcement);
+	}
+
+	/// Prefixes text about a tile with its layer, if the field has more than one.
+	fn in_layer(&self, coords: Coordintes, text: String) -> String {
+		if self.field.get_limit().z > 1 {
+			fl!(
+				"tile-in-layer",
+				layer = self.field.describe_layer(coords.z),
+				tile = text
+			)
+		} else {
+			text
+		}
+	}
+
+	/// Moves the focus cursor to the layer above or below, keeping its position inside the layer.
+	fn change_layer(&mut self, dz: i16) {
+		let layers = self.field.get_limit().z as i16;
+		if layers == 0 {
+			return;
+		}
+
+		self.cursor.z = (self.cursor.z as i16 + dz).clamp(0, layers - 1) as u8;
+		self.announce_tile(self.cursor);
+	}
+
+	/// Moves the focus cursor, stopping at the edges of the field unless they wrap around.
+	fn move_cursor(&mut self, dx: i16, dy: i16) {
+		let limit = self.field.get_limit();
+		if limit.x == 0 || limit.y == 0 {
+			return;
+		}
+
+		let topology = self.field.get_geometry().topology;
+		let x = self.cursor.x as i16 + dx;
+		let y = self.cursor.y as i16 + dy;
+		let x = if topology.wraps_x() {
+			x.rem_euclid(limit.x as i16)
+		} else {
+			x.clamp(0, limit.x as i16 - 1)
+		};
+		let y = if topology.wraps_y() {
+			y.rem_euclid(limit.y as i16)
+		} else {
+			y.clamp(0, limit.y as i16 - 1)
+		};
+		self.cursor = Coordintes {
+			x: x as u8,
+			y: y as u8,
+			z: self.cursor.z,
+		};
+		self.announce_tile(self.cursor);
+	}
+
+	/// Writes the current settings to disk.
+	fn save_config(&self) {
+		if let Some(handler) = &self.config_handler {
+			if let Err(e) = self.config.write_entry(handler) {
+				Error::new(&fl!("error-config-save", error = format!("{:?}", e))).out();
+			}
+		}
+	}
+}
+
+/// Creates an empty field using the settings from the command line or the config.
+/// Settings that cannot make a board fall back to a classic board of the difficulty, after printing why.
+fn new_field(args: &BoardArgs, config: &Config) -> Field {
+	args.new_field(config).unwrap_or_else(|e| {
+		e.out();
+		let (width, height, mines) = config.difficulty.dimensions();
+		Field::new(width, height, mines)
+	})
 }
@@ -0,0 +1,301 @@
+use std::{fs, path::PathBuf};
+
+use cosmic::{
+	iced::widget::scrollable::{Direction, Properties},
+	iced::Length,
+	theme, widget, Element,
+};
+
+use crate::{
+	error::Error,
+	field::{
+		formats,
+		puzzle::{self, Puzzle},
+		tile::Coordintes,
+		Field,
+	},
+	fl,
+	localization::describe_tile,
+};
+
+use super::{app::Message, main_view::TILE_PADDING, skin::TileSet};
+
+pub const EDITOR_SIZES: [u8; 10] = [4, 5, 6, 8, 9, 10, 12, 16, 20, 30];
+
+/// What clicking a tile of the editor changes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EditMode {
+	Mines,
+	Revealed,
+}
+
+impl EditMode {
+	pub const ALL: [EditMode; 2] = [EditMode::Mines, EditMode::Revealed];
+}
+
+/// A flat board being built by hand, shown the way a player would see it at the start.
+pub struct Editor {
+	limit: Coordintes,
+	mines: Vec<u8>,
+	revealed: Vec<bool>,
+	name: String,
+	/// Text of the file field, the extension decides the format.
+	file: String,
+	mode: EditMode,
+	/// Result of the last check or save.
+	status: String,
+	/// The board as it would be played, rebuilt after every change.
+	puzzle: Puzzle,
+
+	modes: Vec<String>,
+	sizes: Vec<String>,
+}
+
+impl Editor {
+	/// Starts from the layout of the given field if it has one, otherwise from an empty board of the same size.
+	pub fn new(field: &Field) -> Editor {
+		let mut limit = field.get_limit();
+		limit.z = 1;
+		let size = limit.x as usize * limit.y as usize;
+
+		// three dimensional fields only keep their first layer
+		let (mines, revealed) = if field.is_initialized() {
+			let tiles = field.get_field();
+			(
+				tiles[..size].iter().map(|t| t.mines.min(1)).collect(),
+				tiles[..size]
+					.iter()
+					.map(|t| t.revealed && !t.is_mine)
+					.collect(),
+			)
+		} else {
+			(vec![0; size], vec![false; size])
+		};
+
+		let mut editor = Editor {
+			limit,
+			mines,
+			revealed,
+			name: String::new(),
+			file: String::new(),
+			mode: EditMode::Mines,
+			status: String::new(),
+			puzzle: Puzzle {
+				name: String::new(),
+				field: Field::new(limit.x, limit.y, 0),
+			},
+			modes: EditMode::ALL
+				.iter()
+				.map(|m| match m {
+					EditMode::Mines => fl!("editor-mode-mines"),
+					EditMode::Revealed => fl!("editor-mode-revealed"),
+				})
+				.collect(),
+			sizes: EDITOR_SIZES.iter().map(|s| s.to_string()).collect(),
+		};
+		editor.rebuild();
+		editor
+	}
+
+	/// The board as it would be played right now.
+	pub fn puzzle(&self) -> &Puzzle {
+		&self.puzzle
+	}
+
+	pub fn set_mode(&mut self, mode: EditMode) {
+		self.mode = mode;
+	}
+
+	pub fn set_name(&mut self, name: String) {
+		self.name = name;
+		self.puzzle.name = self.name.clone();
+	}
+
+	pub fn set_file(&mut self, file: String) {
+		self.file = file;
+	}
+
+	/// Places or removes a mine, or reveals or hides the tile, depending on the mode.
+	pub fn toggle(&mut self, coords: Coordintes) {
+		let index = coords.y as usize * self.limit.x as usize + coords.x as usize;
+		if index >= self.mines.len() {
+			return;
+		}
+
+		match self.mode {
+			EditMode::Mines => {
+				self.mines[index] = 1 - self.mines[index].min(1);
+				// a mine cannot start revealed
+				self.revealed[index] = false;
+			}
+			EditMode::Revealed => {
+				if self.mines[index] == 0 {
+					self.revealed[index] = !self.revealed[index];
+				}
+			}
+		}
+		self.rebuild();
+	}
+
+	/// Changes the size of the board, tiles that still fit keep their state.
+	pub fn resize(&mut self, width: u8, height: u8) {
+		let mut mines = vec![0; width as usize * height as usize];
+		let mut revealed = vec![false; mines.len()];
+		for y in 0..height.min(self.limit.y) as usize {
+			for x in 0..width.min(self.limit.x) as usize {
+				let old = y * self.limit.x as usize + x;
+				let new = y * width as usize + x;
+				mines[new] = self.mines[old];
+				revealed[new] = self.revealed[old];
+			}
+		}
+
+		self.limit.x = width;
+		self.limit.y = height;
+		self.mines = mines;
+		self.revealed = revealed;
+		self.rebuild();
+	}
+
+	pub fn width(&mut self, index: usize) {
+		if let Some(width) = EDITOR_SIZES.get(index) {
+			self.resize(*width, self.limit.y);
+		}
+	}
+
+	pub fn height(&mut self, index: usize) {
+		if let Some(height) = EDITOR_SIZES.get(index) {
+			self.resize(self.limit.x, *height);
+		}
+	}
+
+	/// Runs the solver on the board and shows whether it can be solved without guessing.
+	pub fn check(&mut self) {
+		self.status = if self.puzzle.is_unique() {
+			fl!("editor-unique")
+		} else {
+			fl!("editor-needs-guess")
+		};
+	}
+
+	/// Writes the board to the file, .puzzle files keep the revealed tiles, other formats only the mines.
+	pub fn save(&mut self) {
+		self.status = match self.write() {
+			Ok(path) => fl!("editor-saved", path = path.display().to_string()),
+			Err(e) => e.message().to_string(),
+		};
+	}
+
+	fn write(&self) -> Result<PathBuf, Error> {
+		let path = PathBuf::from(self.file.trim());
+		if path.extension().is_some_and(|e| e == puzzle::EXTENSION) {
+			fs::write(&path, self.puzzle.to_text()).map_err(|e| Error::new(&e.to_string()))?;
+		} else {
+			formats::export(&self.puzzle.field, &path, None)?;
+		}
+		Ok(path)
+	}
+
+	fn rebuild(&mut self) {
+		// the editor never reveals mines, so building the puzzle cannot fail
+		if let Ok(puzzle) = Puzzle::from_parts(&self.name, self.limit, &self.mines, &self.revealed)
+		{
+			self.puzzle = puzzle;
+		}
+		self.status = String::new();
+	}
+
+	/// The board in the main area, clicking a tile edits it.
+	pub fn board_view(&self, tiles: &TileSet, tile_size: u16) -> Element<'static, Message> {
+		let field = &self.puzzle.field;
+		let mut rows = widget::column();
+		for y in 0..self.limit.y {
+			let mut row = widget::row();
+			for x in 0..self.limit.x {
+				let coords = Coordintes { x, y, z: 0 };
+				let tile = match field.get_tile(&coords) {
+					Err(_) => continue,
+					Ok(t) => t,
+				};
+
+				row = row.push(
+					widget::button(widget::icon(tiles.get(tile, true)).size(tile_size))
+						.style(theme::Button::Icon)
+						.padding(TILE_PADDING)
+						.name(fl!("tile-name", row = (y + 1), column = (x + 1)))
+						.description(describe_tile(tile, true))
+						.on_press(Message::EditTile(coords)),
+				);
+			}
+			rows = rows.push(row);
+		}
+
+		let board = widget::scrollable(rows)
+			.direction(Direction::Both {
+				vertical: Properties::default(),
+				horizontal: Properties::default(),
+			})
+			.width(Length::Fill)
+			.height(Length::Fill);
+
+		let mut status = fl!("editor-mines", count = field.get_num_mines());
+		if !self.status.is_empty() {
+			status = format!("{} {}", self.status, status);
+		}
+
+		widget::column()
+			.push(widget::text(status))
+			.push(board)
+			.into()
+	}
+
+	/// The controls in the context drawer.
+	pub fn page(&self) -> Element<Message> {
+		let mode = EditMode::ALL.iter().position(|m| *m == self.mode);
+		let width = EDITOR_SIZES.iter().position(|s| *s == self.limit.x);
+		let height = EDITOR_SIZES.iter().position(|s| *s == self.limit.y);
+
+		let board = widget::settings::view_section(fl!("editor-board"))
+			.add(widget::settings::item(
+				fl!("editor-mode"),
+				widget::dropdown(&self.modes, mode, Message::SetEditMode),
+			))
+			.add(widget::settings::item(
+				fl!("editor-width"),
+				widget::dropdown(&self.sizes, width, Message::SetEditWidth),
+			))
+			.add(widget::settings::item(
+				fl!("editor-height"),
+				widget::dropdown(&self.sizes, height, Message::SetEditHeight),
+			))
+			.add(widget::settings::item(
+				fl!("editor-name"),
+				widget::text_input(fl!("editor-name-placeholder"), self.name.clone())
+					.on_input(Message::EditName),
+			))
+			.add(widget::settings::item(
+				fl!("editor-file"),
+				widget::text_input(fl!("editor-file-placeholder"), self.file.clone())
+					.on_input(Message::EditFile)
+					.on_submit(Message::SaveEdit),
+			));
+
+		let mut save = widget::button::standard(fl!("editor-save"));
+		if !self.file.trim().is_empty() {
+			save = save.on_press(Message::SaveEdit);
+		}
+		let actions = widget::row()
+			.push(widget::button::standard(fl!("editor-check")).on_press(Message::CheckEdit))
+			.push(save)
+			.push(widget::button::suggested(fl!("board-code-play")).on_press(Message::PlayEdit))
+			.push(widget::button::destructive(fl!("editor-close")).on_press(Message::CloseEditor))
+			.spacing(8);
+
+		widget::column()
+			.push(widget::settings::view_column(vec![board.into()]))
+			.push(widget::text(self.status.clone()))
+			.push(actions)
+			.spacing(8)
+			.into()
+	}
+}
@@ -1,7 +0,0 @@
-app-title = Spacemines
-about = About
-new-game = New
-seed = Seed
-options = Options
-view = View
-welcome = Welcome to COSMIC! ✨
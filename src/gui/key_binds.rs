@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use cosmic::iced::keyboard::{key::Named, Key};
+use cosmic::widget::menu::key_bind::{KeyBind, Modifier};
+
+use super::app::MenuAction;
+
+/// Actions on the tile under the focus cursor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BoardAction {
+	Move(i16, i16),
+	// switches to another layer of three dimensional fields
+	Layer(i16),
+	Reveal,
+	Flag,
+	Chord,
+}
+
+/// Key bindings shown in and triggering the menu bar.
+pub fn key_binds() -> HashMap<KeyBind, MenuAction> {
+	let mut key_binds = HashMap::new();
+
+	macro_rules! bind {
+		([$($modifier:ident),* $(,)?], $key:expr, $action:ident) => {{
+			key_binds.insert(
+				KeyBind {
+					modifiers: vec![$(Modifier::$modifier),*],
+					key: $key,
+				},
+				MenuAction::$action,
+			);
+		}};
+	}
+
+	bind!([Ctrl], Key::Character("n".into()), NewGame);
+	bind!([Ctrl, Shift], Key::Character("N".into()), NewSeed);
+	bind!([Ctrl, Shift], Key::Character("V".into()), PasteCode);
+	bind!([Ctrl], Key::Character("d".into()), Daily);
+	bind!([Ctrl], Key::Character("p".into()), Puzzles);
+	bind!([Ctrl], Key::Character("t".into()), Versus);
+	bind!([Ctrl], Key::Character("e".into()), Editor);
+	bind!([Ctrl], Key::Character("z".into()), Undo);
+	bind!([Ctrl], Key::Character("h".into()), Hint);
+	bind!([Ctrl], Key::Character("+".into()), ZoomIn);
+	bind!([Ctrl], Key::Character("=".into()), ZoomIn);
+	bind!([Ctrl], Key::Character("-".into()), ZoomOut);
+	bind!([Ctrl], Key::Character("0".into()), FitWindow);
+	bind!([Ctrl], Key::Character(",".into()), Settings);
+	bind!([Ctrl], Key::Character("i".into()), About);
+
+	key_binds
+}
+
+/// Keys used to play on the board, only handled without modifiers.
+/// Arrow keys, WASD and hjkl all move the cursor, page up/down and q/e switch layers.
+pub fn board_action(key: &Key) -> Option<BoardAction> {
+	match key {
+		Key::Named(Named::ArrowUp) => Some(BoardAction::Move(0, -1)),
+		Key::Named(Named::ArrowDown) => Some(BoardAction::Move(0, 1)),
+		Key::Named(Named::ArrowLeft) => Some(BoardAction::Move(-1, 0)),
+		Key::Named(Named::ArrowRight) => Some(BoardAction::Move(1, 0)),
+		Key::Named(Named::PageUp) => Some(BoardAction::Layer(-1)),
+		Key::Named(Named::PageDown) => Some(BoardAction::Layer(1)),
+		Key::Named(Named::Space) | Key::Named(Named::Enter) => Some(BoardAction::Reveal),
+		Key::Character(c) => match c.as_str() {
+			"w" | "k" => Some(BoardAction::Move(0, -1)),
+			"s" | "j" => Some(BoardAction::Move(0, 1)),
+			"a" | "h" => Some(BoardAction::Move(-1, 0)),
+			"d" | "l" => Some(BoardAction::Move(1, 0)),
+			"q" => Some(BoardAction::Layer(-1)),
+			"e" => Some(BoardAction::Layer(1)),
+			"f" => Some(BoardAction::Flag),
+			"c" => Some(BoardAction::Chord),
+			_ => None,
+		},
+		_ => None,
+	}
+}
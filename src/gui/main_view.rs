@@ -1,24 +1,145 @@
 use cosmic::{
-	widget::{self, icon::Handle, Grid},
-	Element,
+	iced::widget::scrollable::{Direction, Properties},
+	iced::{widget::Space, Length},
+	theme, widget, Element,
 };
 
-use crate::field::{tile::Coordintes, Field};
+use crate::{
+	field::{tile::Coordintes, topology::Grid, Field},
+	fl,
+	localization::describe_tile,
+};
+
+use super::{app::Message, skin::TileSet};
 
-use super::app::Message;
+// space around the icon inside of every tile
+pub const TILE_PADDING: u16 = 2;
 
-pub fn get_field(field: &Field, icon: Handle) -> Element<'static, Message> {
-	let mut grid = Grid::new();
+pub fn get_field(
+	field: &Field,
+	tiles: &TileSet,
+	tile_size: u16,
+	cursor: Coordintes,
+	announcement: &str,
+) -> Element<'static, Message> {
 	let limit = field.get_limit();
-	for x in 0..limit.x {
-		for y in 0..limit.y {
-			let b =
-				widget::button::icon(icon.clone()).on_press(Message::Click(Coordintes { x, y }));
 
-			grid = grid.push(b);
+	// layers of three dimensional fields are laid out next to each other, each with a heading
+	let mut layers = widget::row().spacing(tile_size);
+	for z in 0..limit.z {
+		let mut layer = widget::column();
+		if limit.z > 1 {
+			layer = layer.push(widget::text(field.describe_layer(z)));
+		}
+		layers = layers.push(layer.push(get_layer(field, tiles, tile_size, cursor, z)));
+	}
+
+	// boards larger than the window can be scrolled in both directions
+	let board = widget::scrollable(layers)
+		.direction(Direction::Both {
+			vertical: Properties::default(),
+			horizontal: Properties::default(),
+		})
+		.width(Length::Fill)
+		.height(Length::Fill);
+
+	widget::column()
+		.push(widget::text(status(field, announcement)))
+		.push(board)
+		.into()
+}
+
+fn get_layer(
+	field: &Field,
+	tiles: &TileSet,
+	tile_size: u16,
+	cursor: Coordintes,
+	z: u8,
+) -> Element<'static, Message> {
+	let mut rows = widget::column();
+	let limit = field.get_limit();
+	let hex = field.get_geometry().grid == Grid::Hex;
+	for y in 0..limit.y {
+		let mut row = widget::row();
+
+		// odd hex rows are shifted right by half a tile, so every tile touches two tiles of the rows above and below
+		if hex && y % 2 == 1 {
+			let half = (tile_size + 2 * TILE_PADDING) as f32 / 2.0;
+			row = row.push(Space::with_width(Length::Fixed(half)));
+		}
+
+		for x in 0..limit.x {
+			let coords = Coordintes { x, y, z };
+			let tile = match field.get_tile(&coords) {
+				Err(_) => continue,
+				Ok(t) => t,
+			};
+
+			// buttons only containing an icon have no text a screen reader could read, so name them explicitly
+			let mut name = fl!("tile-name", row = (y + 1), column = (x + 1));
+			if limit.z > 1 {
+				name = fl!(
+					"tile-in-layer",
+					layer = field.describe_layer(z),
+					tile = name
+				);
+			}
+			let mut b =
+				widget::button(widget::icon(tiles.get(tile, field.has_lost())).size(tile_size))
+					.style(theme::Button::Icon)
+					.padding(TILE_PADDING)
+					.name(name)
+					.description(describe_tile(tile, field.has_lost()));
+
+			if !field.has_lost() && !field.victory() {
+				b = b.on_press(Message::Click(coords));
+			}
+
+			// mines found in versus games are coloured by the player who found them, suggested is left to the cursor
+			b = match tile.owner {
+				None => b,
+				Some(0) => b.style(theme::Button::Standard),
+				Some(_) => b.style(theme::Button::Destructive),
+			};
+
+			// highlight the tile focused for keyboard play
+			if coords == cursor {
+				b = b.style(theme::Button::Suggested);
+			}
+
+			row = row.push(b);
 		}
-		grid = grid.insert_row();
+		rows = rows.push(row);
 	}
 
-	grid.into()
+	rows.into()
+}
+
+/// Line above the board announcing the result of the last move and the game state.
+fn status(field: &Field, announcement: &str) -> String {
+	let mut state = if field.has_lost() {
+		fl!("game-lost")
+	} else if field.is_initialized() && field.victory() {
+		fl!("game-won")
+	} else {
+		let remaining = field.get_num_mines() as i32 - field.num_flags() as i32;
+		fl!("mines-remaining", count = remaining)
+	};
+
+	for legend in field.legend() {
+		state = format!("{} {}", state, legend);
+	}
+
+	// the code is only shown after the game, so it does not give away anything while playing
+	if field.has_lost() || (field.is_initialized() && field.victory()) {
+		if let Some(code) = field.board_code() {
+			state = format!("{} {}", state, fl!("board-code", code = code.to_string()));
+		}
+	}
+
+	if announcement.is_empty() {
+		state
+	} else {
+		format!("{} {}", announcement, state)
+	}
 }
@@ -1,7 +1,11 @@
 mod about;
 mod app;
-mod localization;
+mod editor;
+mod key_binds;
 mod main_view;
+mod puzzles;
+mod settings;
+mod skin;
 
 use crate::error::Error;
 
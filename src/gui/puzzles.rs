@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+use cosmic::{widget, Element};
+
+use crate::{
+	field::puzzle::{self, Puzzle},
+	fl,
+};
+
+use super::app::Message;
+
+/// One file of the puzzle directory.
+struct Entry {
+	name: String,
+	/// Why the puzzle is special or broken, empty for puzzles that can be solved without guessing.
+	description: String,
+	/// `None` if the file could not be read as a puzzle.
+	puzzle: Option<Puzzle>,
+}
+
+/// Lists the puzzles of a directory, read again every time the browser is opened.
+pub struct PuzzlePage {
+	dir: Option<PathBuf>,
+	entries: Vec<Entry>,
+	/// Shown instead of the list if the directory could not be read.
+	error: Option<String>,
+}
+
+impl PuzzlePage {
+	pub fn new(dir: Option<PathBuf>) -> PuzzlePage {
+		PuzzlePage {
+			dir: dir.or_else(puzzle::default_dir),
+			entries: Vec::new(),
+			error: None,
+		}
+	}
+
+	pub fn reload(&mut self) {
+		self.entries.clear();
+		self.error = None;
+
+		let dir = match &self.dir {
+			None => {
+				self.error = Some(fl!("error-puzzle-dir"));
+				return;
+			}
+			Some(d) => d,
+		};
+		let puzzles = match puzzle::load_dir(dir) {
+			Err(_) => {
+				self.error = Some(fl!("puzzles-empty", dir = dir.display().to_string()));
+				return;
+			}
+			Ok(p) => p,
+		};
+
+		self.entries = puzzles
+			.into_iter()
+			.map(|(path, puzzle)| match puzzle {
+				Ok(p) => Entry {
+					name: p.name.clone(),
+					description: if p.is_unique() {
+						String::new()
+					} else {
+						fl!("puzzle-needs-guess-short")
+					},
+					puzzle: Some(p),
+				},
+				Err(e) => Entry {
+					name: path.display().to_string(),
+					description: e.message().to_string(),
+					puzzle: None,
+				},
+			})
+			.collect();
+		if self.entries.is_empty() {
+			self.error = Some(fl!("puzzles-empty", dir = dir.display().to_string()));
+		}
+	}
+
+	pub fn get(&self, index: usize) -> Option<&Puzzle> {
+		self.entries.get(index)?.puzzle.as_ref()
+	}
+
+	pub fn view(&self) -> Element<Message> {
+		if let Some(error) = &self.error {
+			return widget::text::body(error.clone()).into();
+		}
+
+		let mut section = widget::settings::view_section(fl!("puzzles"));
+		for (i, entry) in self.entries.iter().enumerate() {
+			let mut play = widget::button::standard(fl!("board-code-play"));
+			if entry.puzzle.is_some() {
+				play = play.on_press(Message::PlayPuzzle(i));
+			}
+
+			let mut item = widget::settings::item::builder(entry.name.clone());
+			if !entry.description.is_empty() {
+				item = item.description(entry.description.clone());
+			}
+			section = section.add(item.control(play));
+		}
+
+		widget::settings::view_column(vec![section.into()]).into()
+	}
+}
@@ -0,0 +1,211 @@
+use cosmic::{widget, Element};
+
+use crate::{
+	config::{Config, Difficulty, Skin, Theme},
+	field::{
+		rules::Rule,
+		topology::{Grid, Neighbourhood, Topology},
+		SafeStart,
+	},
+	fl,
+};
+
+use super::app::Message;
+
+pub const SAFE_STARTS: [SafeStart; 4] = [
+	SafeStart::Tile,
+	SafeStart::Relocate,
+	SafeStart::Opening,
+	SafeStart::NoGuess,
+];
+pub const TOPOLOGIES: [Topology; 3] = [Topology::Rectangle, Topology::Cylinder, Topology::Torus];
+pub const GRIDS: [Grid; 2] = [Grid::Square, Grid::Hex];
+pub const LAYERS: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+pub const MINES_PER_TILE: [u8; 5] = [1, 2, 3, 4, 5];
+// also used as the steps when zooming
+pub const TILE_SIZES: [u16; 10] = [12, 16, 20, 24, 32, 40, 48, 64, 80, 96];
+
+/// Holds the labels of all dropdowns, since they have to outlive the view.
+pub struct SettingsPage {
+	difficulties: Vec<String>,
+	safe_starts: Vec<String>,
+	topologies: Vec<String>,
+	grids: Vec<String>,
+	layers: Vec<String>,
+	neighbourhoods: Vec<String>,
+	mines_per_tile: Vec<String>,
+	rules: Vec<String>,
+	themes: Vec<String>,
+	skins: Vec<String>,
+	tile_sizes: Vec<String>,
+}
+
+impl SettingsPage {
+	pub fn new() -> SettingsPage {
+		SettingsPage {
+			difficulties: Difficulty::ALL
+				.iter()
+				.map(|d| match d {
+					Difficulty::Beginner => fl!("beginner"),
+					Difficulty::Intermediate => fl!("intermediate"),
+					Difficulty::Expert => fl!("expert"),
+				})
+				.collect(),
+			safe_starts: SAFE_STARTS
+				.iter()
+				.map(|s| match s {
+					SafeStart::Tile => fl!("safe-start-tile"),
+					SafeStart::Opening => fl!("safe-start-opening"),
+					SafeStart::NoGuess => fl!("safe-start-no-guess"),
+					SafeStart::Relocate => fl!("safe-start-relocate"),
+				})
+				.collect(),
+			topologies: TOPOLOGIES
+				.iter()
+				.map(|t| match t {
+					Topology::Rectangle => fl!("topology-rectangle"),
+					Topology::Cylinder => fl!("topology-cylinder"),
+					Topology::Torus => fl!("topology-torus"),
+				})
+				.collect(),
+			grids: GRIDS
+				.iter()
+				.map(|g| match g {
+					Grid::Square => fl!("grid-square"),
+					Grid::Hex => fl!("grid-hex"),
+				})
+				.collect(),
+			layers: LAYERS.iter().map(|l| l.to_string()).collect(),
+			mines_per_tile: MINES_PER_TILE.iter().map(|m| m.to_string()).collect(),
+			// custom offsets can only be set on the command line or in the config, so they are not listed
+			neighbourhoods: Neighbourhood::PRESETS
+				.iter()
+				.map(|n| match n {
+					Neighbourhood::Moore => fl!("neighbourhood-moore"),
+					Neighbourhood::VonNeumann => fl!("neighbourhood-von-neumann"),
+					Neighbourhood::Knight => fl!("neighbourhood-knight"),
+					Neighbourhood::Radius2 | Neighbourhood::Custom(_) => {
+						fl!("neighbourhood-radius-2")
+					}
+				})
+				.collect(),
+			rules: Rule::ALL
+				.iter()
+				.map(|r| match r {
+					Rule::Classic => fl!("rule-classic"),
+					Rule::Liar => fl!("rule-liar"),
+					Rule::Double => fl!("rule-double"),
+					Rule::Negative => fl!("rule-negative"),
+					Rule::Orthogonal => fl!("rule-orthogonal"),
+				})
+				.collect(),
+			themes: Theme::ALL
+				.iter()
+				.map(|t| match t {
+					Theme::System => fl!("theme-system"),
+					Theme::Light => fl!("theme-light"),
+					Theme::Dark => fl!("theme-dark"),
+				})
+				.collect(),
+			skins: Skin::ALL
+				.iter()
+				.map(|s| match s {
+					Skin::Classic => fl!("skin-classic"),
+					Skin::Flat => fl!("skin-flat"),
+					Skin::HighContrast => fl!("skin-high-contrast"),
+					Skin::Colorblind => fl!("skin-colorblind"),
+				})
+				.collect(),
+			tile_sizes: TILE_SIZES.iter().map(|s| format!("{} px", s)).collect(),
+		}
+	}
+
+	pub fn view(&self, config: &Config) -> Element<Message> {
+		let difficulty = Difficulty::ALL.iter().position(|d| *d == config.difficulty);
+		let safe_start = SAFE_STARTS.iter().position(|s| *s == config.safe_start);
+		let topology = TOPOLOGIES.iter().position(|t| *t == config.topology);
+		let grid = GRIDS.iter().position(|g| *g == config.grid);
+		let layers = LAYERS.iter().position(|l| *l == config.layers);
+		let mines_per_tile = MINES_PER_TILE
+			.iter()
+			.position(|m| *m == config.mines_per_tile);
+		let neighbourhood = Neighbourhood::PRESETS
+			.iter()
+			.position(|n| *n == config.neighbourhood);
+		let rule = Rule::ALL.iter().position(|r| *r == config.rule);
+		let theme = Theme::ALL.iter().position(|t| *t == config.theme);
+		let skin = Skin::ALL.iter().position(|s| *s == config.skin);
+		let tile_size = TILE_SIZES.iter().position(|s| *s == config.tile_size);
+
+		let gameplay = widget::settings::view_section(fl!("gameplay"))
+			.add(widget::settings::item(
+				fl!("difficulty"),
+				widget::dropdown(&self.difficulties, difficulty, Message::SetDifficulty),
+			))
+			.add(widget::settings::item(
+				fl!("safe-start"),
+				widget::dropdown(&self.safe_starts, safe_start, Message::SetSafeStart),
+			))
+			.add(widget::settings::item(
+				fl!("topology"),
+				widget::dropdown(&self.topologies, topology, Message::SetTopology),
+			))
+			.add(widget::settings::item(
+				fl!("grid"),
+				widget::dropdown(&self.grids, grid, Message::SetGrid),
+			))
+			.add(widget::settings::item(
+				fl!("layers"),
+				widget::dropdown(&self.layers, layers, Message::SetLayers),
+			))
+			.add(widget::settings::item(
+				fl!("neighbourhood"),
+				widget::dropdown(
+					&self.neighbourhoods,
+					neighbourhood,
+					Message::SetNeighbourhood,
+				),
+			))
+			.add(widget::settings::item(
+				fl!("mines-per-tile"),
+				widget::dropdown(
+					&self.mines_per_tile,
+					mines_per_tile,
+					Message::SetMinesPerTile,
+				),
+			))
+			.add(widget::settings::item(
+				fl!("rule"),
+				widget::dropdown(&self.rules, rule, Message::SetRule),
+			))
+			.add(widget::settings::item(
+				fl!("daily-salt"),
+				widget::text_input(fl!("daily-salt-placeholder"), config.daily_salt.clone())
+					.on_input(Message::SetDailySalt),
+			))
+			.add(widget::settings::item(
+				fl!("question-marks"),
+				widget::toggler(None, config.question_marks, Message::SetQuestionMarks),
+			))
+			.add(widget::settings::item(
+				fl!("confirm-prompts"),
+				widget::toggler(None, config.confirm_prompts, Message::SetConfirmPrompts),
+			));
+
+		let appearance = widget::settings::view_section(fl!("appearance"))
+			.add(widget::settings::item(
+				fl!("theme"),
+				widget::dropdown(&self.themes, theme, Message::SetTheme),
+			))
+			.add(widget::settings::item(
+				fl!("skin"),
+				widget::dropdown(&self.skins, skin, Message::SetSkin),
+			))
+			.add(widget::settings::item(
+				fl!("tile-size"),
+				widget::dropdown(&self.tile_sizes, tile_size, Message::SetTileSize),
+			));
+
+		widget::settings::view_column(vec![gameplay.into(), appearance.into()]).into()
+	}
+}
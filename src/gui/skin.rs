@@ -0,0 +1,364 @@
+use cosmic::{theme, widget::icon::Handle};
+use rust_embed::RustEmbed;
+
+use crate::{config::Skin, field::tile::Tile};
+
+#[derive(RustEmbed)]
+#[folder = "src/gui/skins"]
+struct SkinFiles;
+
+/// Colors filled into the placeholders of the svg templates.
+struct Palette {
+	hidden: &'static str,
+	revealed: &'static str,
+	highlight: &'static str,
+	shadow: &'static str,
+	border: &'static str,
+	// flag pole and question mark, drawn on top of hidden tiles
+	mark: &'static str,
+	flag: &'static str,
+	mine: &'static str,
+	numbers: [&'static str; 8],
+}
+
+// classic numbers as known from the original game
+const CLASSIC_NUMBERS: [&str; 8] = [
+	"#0000ff", "#008000", "#ff0000", "#000080", "#800000", "#008080", "#000000", "#808080",
+];
+const CLASSIC_NUMBERS_DARK: [&str; 8] = [
+	"#6b8cff", "#5fd35f", "#ff6b6b", "#a58cff", "#ff9f6b", "#5fd3d3", "#e0e0e0", "#a0a0a0",
+];
+
+// based on the Okabe-Ito palette, which stays distinguishable for all common color vision deficiencies
+const COLORBLIND_NUMBERS: [&str; 8] = [
+	"#0072b2", "#009e73", "#d55e00", "#cc79a7", "#e69f00", "#56b4e9", "#000000", "#999999",
+];
+const COLORBLIND_NUMBERS_DARK: [&str; 8] = [
+	"#56b4e9", "#009e73", "#e69f00", "#cc79a7", "#d55e00", "#f0e442", "#ffffff", "#999999",
+];
+
+fn folder(skin: Skin) -> &'static str {
+	match skin {
+		Skin::Classic => "classic",
+		Skin::Flat | Skin::Colorblind => "flat",
+		Skin::HighContrast => "high-contrast",
+	}
+}
+
+fn palette(skin: Skin, dark: bool) -> Palette {
+	match (skin, dark) {
+		(Skin::Classic, false) => Palette {
+			hidden: "#c0c0c0",
+			revealed: "#c6c6c6",
+			highlight: "#ffffff",
+			shadow: "#808080",
+			border: "#7b7b7b",
+			mark: "#000000",
+			flag: "#ff0000",
+			mine: "#000000",
+			numbers: CLASSIC_NUMBERS,
+		},
+		(Skin::Classic, true) => Palette {
+			hidden: "#4a4a4a",
+			revealed: "#333333",
+			highlight: "#6e6e6e",
+			shadow: "#2a2a2a",
+			border: "#1e1e1e",
+			mark: "#e0e0e0",
+			flag: "#ff5555",
+			mine: "#e0e0e0",
+			numbers: CLASSIC_NUMBERS_DARK,
+		},
+		(Skin::Flat, false) => Palette {
+			hidden: "#94a3b8",
+			revealed: "#e2e8f0",
+			highlight: "#94a3b8",
+			shadow: "#94a3b8",
+			border: "#475569",
+			mark: "#1e293b",
+			flag: "#dc2626",
+			mine: "#1e293b",
+			numbers: [
+				"#2563eb", "#16a34a", "#dc2626", "#7c3aed", "#b45309", "#0891b2", "#1e293b",
+				"#64748b",
+			],
+		},
+		(Skin::Flat, true) => Palette {
+			hidden: "#475569",
+			revealed: "#1e293b",
+			highlight: "#475569",
+			shadow: "#475569",
+			border: "#cbd5e1",
+			mark: "#f1f5f9",
+			flag: "#f87171",
+			mine: "#f1f5f9",
+			numbers: [
+				"#60a5fa", "#4ade80", "#f87171", "#c084fc", "#fbbf24", "#22d3ee", "#f1f5f9",
+				"#94a3b8",
+			],
+		},
+		(Skin::HighContrast, false) => Palette {
+			hidden: "#000000",
+			revealed: "#ffffff",
+			highlight: "#000000",
+			shadow: "#000000",
+			border: "#000000",
+			mark: "#ffffff",
+			flag: "#ffff00",
+			mine: "#000000",
+			numbers: ["#000000"; 8],
+		},
+		(Skin::HighContrast, true) => Palette {
+			hidden: "#ffffff",
+			revealed: "#000000",
+			highlight: "#ffffff",
+			shadow: "#ffffff",
+			border: "#ffffff",
+			mark: "#000000",
+			flag: "#0000ff",
+			mine: "#ffffff",
+			numbers: ["#ffffff"; 8],
+		},
+		(Skin::Colorblind, false) => Palette {
+			numbers: COLORBLIND_NUMBERS,
+			flag: "#d55e00",
+			..palette(Skin::Flat, false)
+		},
+		(Skin::Colorblind, true) => Palette {
+			numbers: COLORBLIND_NUMBERS_DARK,
+			flag: "#e69f00",
+			..palette(Skin::Flat, true)
+		},
+	}
+}
+
+/*
+Digits are drawn as seven segment displays made out of rectangles,
+text would depend on the fonts installed on the system
+*/
+fn segments(active: &[usize]) -> String {
+	const SEGMENTS: [(u8, u8, u8, u8); 7] = [
+		(5, 3, 6, 2),  // top
+		(9, 3, 2, 6),  // top right
+		(9, 7, 2, 6),  // bottom right
+		(5, 11, 6, 2), // bottom
+		(5, 7, 2, 6),  // bottom left
+		(5, 3, 2, 6),  // top left
+		(5, 7, 6, 2),  // middle
+	];
+
+	active
+		.iter()
+		.map(|i| {
+			let (x, y, w, h) = SEGMENTS[*i];
+			format!(
+				"<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+				x, y, w, h
+			)
+		})
+		.collect()
+}
+
+fn digit(value: u8) -> String {
+	segments(match value {
+		0 => &[0, 1, 2, 3, 4, 5],
+		1 => &[1, 2],
+		2 => &[0, 1, 6, 4, 3],
+		3 => &[0, 1, 6, 2, 3],
+		4 => &[5, 6, 1, 2],
+		5 => &[0, 5, 6, 2, 3],
+		6 => &[0, 5, 6, 4, 2, 3],
+		7 => &[0, 1, 2],
+		9 => &[0, 1, 2, 3, 5, 6],
+		_ => &[0, 1, 2, 3, 4, 5, 6],
+	})
+}
+
+// numbers with a sign or more than one digit have their digits shrunk to fit next to each other
+fn number(value: i16) -> String {
+	if (1..=9).contains(&value) {
+		return digit(value as u8);
+	}
+
+	let mut glyphs = Vec::new();
+	if value < 0 {
+		glyphs.push(segments(&[6]));
+	}
+	for c in value.unsigned_abs().to_string().bytes() {
+		glyphs.push(digit(c - b'0'));
+	}
+
+	// a single digit is 6 by 10 starting at 5, 3, the tile leaves room for 12 in width
+	let slot = 12.0 / glyphs.len() as f32;
+	let scale = (slot / 7.0).min(1.0);
+	glyphs
+		.iter()
+		.enumerate()
+		.map(|(i, g)| {
+			format!(
+				"<g transform=\"translate({} {}) scale({}) translate(-5 -3)\">{}</g>",
+				2.0 + i as f32 * slot + (slot - 6.0 * scale) / 2.0,
+				3.0 + (10.0 - 10.0 * scale) / 2.0,
+				scale,
+				g
+			)
+		})
+		.collect()
+}
+
+fn render(skin: Skin, name: &str, palette: &Palette, number: Option<i16>) -> String {
+	let path = format!("{}/{}.svg", folder(skin), name);
+	let template = match SkinFiles::get(&path) {
+		// every skin ships all files, so this only happens if the embed is broken
+		None => panic!("missing skin file {}", path),
+		Some(file) => String::from_utf8_lossy(&file.data).into_owned(),
+	};
+
+	let mut svg = template
+		.replace("{hidden}", palette.hidden)
+		.replace("{revealed}", palette.revealed)
+		.replace("{highlight}", palette.highlight)
+		.replace("{shadow}", palette.shadow)
+		.replace("{border}", palette.border)
+		.replace("{mark}", palette.mark)
+		.replace("{flag}", palette.flag)
+		.replace("{mine}", palette.mine);
+
+	if let Some(n) = number {
+		svg = svg
+			.replace(
+				"{number}",
+				palette.numbers[(n.unsigned_abs() as usize).clamp(1, 8) - 1],
+			)
+			.replace("{digit}", &number(n));
+	}
+
+	svg
+}
+
+fn load(skin: Skin, name: &str, palette: &Palette, number: Option<i16>) -> Handle {
+	cosmic::widget::icon::from_svg_bytes(render(skin, name, palette, number).into_bytes())
+}
+
+// adds a small number to the bottom right corner, for tiles holding multiple flags or mines
+// numbers of several digits take the whole width of the tile, so they start further left
+fn load_counted(skin: Skin, name: &str, palette: &Palette, count: u8, color: &str) -> Handle {
+	let left = if count > 9 { 7 } else { 9 };
+	let badge = format!(
+		"<g fill=\"{}\" transform=\"translate({} 9) scale(0.5)\">{}</g></svg>",
+		color,
+		left,
+		number(count as i16)
+	);
+	let svg = render(skin, name, palette, None).replace("</svg>", &badge);
+	cosmic::widget::icon::from_svg_bytes(svg.into_bytes())
+}
+
+/// Icons for every state a tile can be in, for one skin and theme variant.
+pub struct TileSet {
+	hidden: Handle,
+	revealed: Handle,
+	flag: Handle,
+	unknown: Handle,
+	mine: Handle,
+	numbers: Vec<Handle>,
+	// numbers outside of 1 to 8 only happen with some rules, so they are rendered when needed
+	skin: Skin,
+	palette: Palette,
+	// 2 to 9, only used when tiles can hold multiple mines, larger counts are rendered when needed
+	flag_counts: Vec<Handle>,
+	mine_counts: Vec<Handle>,
+}
+
+impl TileSet {
+	fn new(skin: Skin, dark: bool) -> TileSet {
+		let palette = palette(skin, dark);
+		TileSet {
+			skin,
+			hidden: load(skin, "hidden", &palette, None),
+			revealed: load(skin, "revealed", &palette, None),
+			flag: load(skin, "flag", &palette, None),
+			unknown: load(skin, "unknown", &palette, None),
+			mine: load(skin, "mine", &palette, None),
+			numbers: (1..=8)
+				.map(|n| load(skin, "number", &palette, Some(n)))
+				.collect(),
+			flag_counts: (2..=9)
+				.map(|n| load_counted(skin, "flag", &palette, n, palette.mark))
+				.collect(),
+			mine_counts: (2..=9)
+				.map(|n| load_counted(skin, "mine", &palette, n, palette.flag))
+				.collect(),
+			palette,
+		}
+	}
+
+	pub fn get(&self, tile: &Tile, show_mines: bool) -> Handle {
+		// mines found in versus games are revealed without the game being lost
+		let show_mines = show_mines || tile.owner.is_some();
+		if tile.flags > 9 {
+			load_counted(
+				self.skin,
+				"flag",
+				&self.palette,
+				tile.flags,
+				self.palette.mark,
+			)
+		} else if tile.flags > 1 {
+			self.flag_counts[(tile.flags - 2) as usize].clone()
+		} else if tile.flag {
+			self.flag.clone()
+		} else if tile.unknown {
+			self.unknown.clone()
+		} else if tile.mines > 9 && show_mines {
+			load_counted(
+				self.skin,
+				"mine",
+				&self.palette,
+				tile.mines,
+				self.palette.flag,
+			)
+		} else if tile.mines > 1 && show_mines {
+			self.mine_counts[(tile.mines - 2) as usize].clone()
+		} else if tile.is_mine && show_mines {
+			self.mine.clone()
+		} else if tile.revealed {
+			match tile.shown {
+				0 => self.revealed.clone(),
+				v @ 1..=8 => self.numbers[(v - 1) as usize].clone(),
+				v => load(self.skin, "number", &self.palette, Some(v)),
+			}
+		} else {
+			self.hidden.clone()
+		}
+	}
+}
+
+/// Both variants of a skin, so switching between light and dark does not require reloading.
+pub struct Tiles {
+	light: TileSet,
+	dark: TileSet,
+}
+
+impl Tiles {
+	pub fn new(skin: Skin) -> Tiles {
+		Tiles {
+			light: TileSet::new(skin, false),
+			dark: TileSet::new(skin, true),
+		}
+	}
+
+	/// The variant matching the currently active theme.
+	pub fn current(&self) -> &TileSet {
+		if theme::active().cosmic().is_dark {
+			&self.dark
+		} else {
+			&self.light
+		}
+	}
+}
+
+/// Icon of the app, the mine of the classic skin.
+pub fn app_icon() -> Handle {
+	load(Skin::Classic, "mine", &palette(Skin::Classic, false), None)
+}
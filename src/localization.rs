@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use i18n_embed::{
+	fluent::{fluent_language_loader, FluentLanguageLoader},
+	unic_langid::{CharacterDirection, LanguageIdentifier},
+	DesktopLanguageRequester, LanguageLoader,
+};
+use once_cell::sync::Lazy;
+use rust_embed::RustEmbed;
+
+use crate::{error::Error, field::tile::Tile};
+
+#[derive(RustEmbed)]
+#[folder = "i18n"]
+struct Localizations;
+
+pub static LANGUAGE_LOADER: Lazy<FluentLanguageLoader> = Lazy::new(|| {
+	let loader: FluentLanguageLoader = fluent_language_loader!();
+
+	loader
+		.load_fallback_language(&Localizations)
+		.expect("Error while loading fallback language");
+	// errors while parsing the arguments are shown before init, see there for why this is off
+	loader.set_use_isolating(false);
+
+	loader
+});
+
+#[macro_export]
+macro_rules! fl {
+    ($message_id:literal) => {{
+        i18n_embed_fl::fl!($crate::localization::LANGUAGE_LOADER, $message_id)
+    }};
+
+    ($message_id:literal, $($args:expr),*) => {{
+        i18n_embed_fl::fl!($crate::localization::LANGUAGE_LOADER, $message_id, $($args), *)
+    }};
+}
+
+// selects the language given on the command line, or the ones requested by the environment if there is none
+pub fn init(lang: Option<&str>) {
+	let requested = match lang.map(str::parse::<LanguageIdentifier>) {
+		None => DesktopLanguageRequester::requested_languages(),
+		Some(Ok(lang)) => vec![lang],
+		Some(Err(_)) => {
+			Error::new(&crate::fl!(
+				"error-language",
+				lang = lang.unwrap_or_default()
+			))
+			.out();
+			DesktopLanguageRequester::requested_languages()
+		}
+	};
+
+	if let Err(e) = i18n_embed::select(&*LANGUAGE_LOADER, &Localizations, &requested) {
+		Error::new(&e.to_string()).out();
+	}
+
+	// isolation marks are only needed to keep numbers in right-to-left text in order,
+	// elsewhere they just show up as garbage in many terminals
+	let rtl = LANGUAGE_LOADER
+		.current_languages()
+		.first()
+		.map(|l| l.character_direction() == CharacterDirection::RTL)
+		.unwrap_or(false);
+	LANGUAGE_LOADER.set_use_isolating(rtl);
+}
+
+/// Text describing the state of a single tile, used by screen readers and the describe mode of the TUI.
+pub fn describe_tile(tile: &Tile, show_mines: bool) -> String {
+	if tile.flag {
+		crate::fl!("tile-flagged", count = tile.flags)
+	} else if tile.unknown {
+		crate::fl!("tile-unknown")
+	} else if let Some(player) = tile.owner {
+		crate::fl!("tile-found", player = (player + 1), count = tile.mines)
+	} else if tile.is_mine && show_mines {
+		crate::fl!("tile-mine", count = tile.mines)
+	} else if tile.revealed {
+		crate::fl!("tile-revealed", value = tile.shown)
+	} else {
+		crate::fl!("tile-hidden")
+	}
+}
+
+#[cfg(test)]
+mod localization_tests {
+	use std::collections::BTreeSet;
+
+	use super::*;
+
+	// ids of all messages in a fluent file, attributes and variants are indented and skipped
+	fn message_ids(file: &str) -> BTreeSet<String> {
+		let data = Localizations::get(file).unwrap().data;
+		String::from_utf8_lossy(&data)
+			.lines()
+			.filter(|l| l.starts_with(|c: char| c.is_ascii_alphabetic()))
+			.filter_map(|l| l.split_once('='))
+			.map(|(id, _)| id.trim().to_string())
+			.collect()
+	}
+
+	#[test]
+	fn test_all_locales_complete() -> Result<(), Error> {
+		let fallback = message_ids("en/spacemines.ftl");
+		if fallback.is_empty() {
+			return Err(Error::new("fallback language has no messages"));
+		}
+
+		let mut locales = 0;
+		for file in Localizations::iter() {
+			locales += 1;
+			let ids = message_ids(&file);
+			let missing: Vec<_> = fallback.difference(&ids).collect();
+			let extra: Vec<_> = ids.difference(&fallback).collect();
+			if !missing.is_empty() || !extra.is_empty() {
+				eprintln!("{}: missing {:?}, unknown {:?}", file, missing, extra);
+				return Err(Error::new("locales do not have the same messages"));
+			}
+		}
+
+		if locales < 3 {
+			return Err(Error::new("expected at least three locales"));
+		}
+
+		Ok(())
+	}
+}
@@ -1,6 +1,12 @@
 mod args;
+mod bot;
+mod cli;
+mod config;
+mod daily;
 mod error;
 mod field;
+mod localization;
+mod race;
 
 #[cfg(feature = "gui")]
 mod gui;
@@ -10,11 +16,27 @@ mod tui;
 
 #[cfg(all(feature = "tui", not(feature = "gui")))]
 fn main() {
+	use args::Args;
+	use clap::Parser;
+
+	let args = Args::parse();
+	localization::init(args.lang.as_deref());
+	if run_command(&args) {
+		return;
+	}
 	tui::run_tui();
 }
 
 #[cfg(all(feature = "gui", not(feature = "tui")))]
 fn main() {
+	use args::Args;
+	use clap::Parser;
+
+	let args = Args::parse();
+	localization::init(args.lang.as_deref());
+	if run_command(&args) {
+		return;
+	}
 	if let Err(e) = gui::run_gui() {
 		e.fatal()
 	}
@@ -26,13 +48,30 @@ fn main() {
 	use clap::Parser;
 
 	let args = Args::parse();
-	if args.tui {
+	localization::init(args.lang.as_deref());
+	if run_command(&args) {
+		return;
+	}
+	if args.play().tui {
 		tui::run_tui()
 	} else if let Err(e) = gui::run_gui() {
 		e.fatal()
 	}
 }
 
+// runs subcommands that do not need a user interface, returns false if a game should be played
+fn run_command(args: &args::Args) -> bool {
+	match &args.command {
+		None | Some(args::Command::Play(_)) => false,
+		Some(command) => {
+			if let Err(e) = cli::run(command) {
+				e.fatal();
+			}
+			true
+		}
+	}
+}
+
 #[cfg(all(not(feature = "tui"), not(feature = "gui")))]
 compile_error!("Either feature \"tui\" or feature \"gui\" must be enabled");
 #[cfg(all(not(feature = "tui"), not(feature = "gui")))]
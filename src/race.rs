@@ -0,0 +1,518 @@
+use std::{
+	io::{BufRead, BufReader, Write},
+	net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream},
+	sync::mpsc::{self, Receiver, Sender},
+	thread,
+	time::{Duration, Instant},
+};
+
+use crate::{
+	args::{BoardArgs, PlayArgs},
+	config::Config,
+	error::Error,
+	field::{code::BoardCode, tile::Coordintes, Field},
+	fl,
+};
+use serde::{Deserialize, Serialize};
+
+/*
+Races over TCP, everyone plays the board of the same code and sees how far the others are.
+
+The host waits for all players, then sends the code. Players report their progress after every move,
+the host adds the time of finishing and sends the standings of everyone back. Hitting a mine starts the board over
+and counts as a death. The race is over once every player finished or left.
+Messages are JSON objects, one per line:
+	player: {"type":"join","version":1,"name":"alice"}
+	host:   {"type":"waiting","joined":1,"players":2}
+	host:   {"type":"start","code":"..."}
+	player: {"type":"progress","cleared":0.25,"deaths":0,"finished":false}
+	host:   {"type":"standings","over":false,"players":[{"name":"alice","cleared":0.25,"deaths":0,"time":null,"left":false}]}
+*/
+const RACE_VERSION: u8 = 1;
+
+// how long the host waits for a new connection to say who it is
+const JOIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+// every message sent in either direction, the type is written next to the fields
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Message {
+	Join {
+		version: u8,
+		name: String,
+	},
+	Waiting {
+		joined: usize,
+		players: usize,
+	},
+	Start {
+		code: String,
+	},
+	Progress {
+		cleared: f64,
+		deaths: u32,
+		finished: bool,
+	},
+	Standings {
+		over: bool,
+		players: Vec<Standing>,
+	},
+}
+
+// how far a player is, time is the number of seconds it took to finish the board
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Standing {
+	pub name: String,
+	pub cleared: f64,
+	pub deaths: u32,
+	pub time: Option<f64>,
+	#[serde(default)]
+	pub left: bool,
+}
+
+impl Standing {
+	fn new(name: String) -> Standing {
+		Standing {
+			name,
+			cleared: 0.0,
+			deaths: 0,
+			time: None,
+			left: false,
+		}
+	}
+
+	// one line for the standings shown to the players
+	pub fn describe(&self) -> String {
+		if self.left {
+			return fl!("race-left", name = self.name.as_str());
+		}
+		match self.time {
+			Some(time) => fl!(
+				"race-finished",
+				name = self.name.as_str(),
+				time = format!("{:.1}", time),
+				deaths = self.deaths
+			),
+			None => fl!(
+				"race-player",
+				name = self.name.as_str(),
+				cleared = format!("{:.0}", self.cleared * 100.0),
+				deaths = self.deaths
+			),
+		}
+	}
+}
+
+fn send(stream: &mut TcpStream, message: &Message) -> bool {
+	let line = match serde_json::to_string(message) {
+		Ok(l) => l,
+		Err(_) => return false,
+	};
+	writeln!(stream, "{}", line)
+		.and_then(|_| stream.flush())
+		.is_ok()
+}
+
+// lines that are no message are skipped, the other side might be newer
+fn parse(line: &str) -> Option<Message> {
+	serde_json::from_str(line.trim()).ok()
+}
+
+// the share of safe tiles that are revealed
+fn cleared(field: &Field) -> f64 {
+	let tiles = field.get_field();
+	let safe = tiles.iter().filter(|t| !t.is_mine).count();
+	let revealed = tiles.iter().filter(|t| t.revealed && !t.is_mine).count();
+	revealed as f64 / safe.max(1) as f64
+}
+
+/*
+The board of a race, from the code or daily challenge of the arguments, or from the settings with the first click in the middle.
+Layouts from files have no code, so they cannot be raced
+*/
+pub fn board(args: &BoardArgs, config: &Config) -> Result<BoardCode, Error> {
+	let mut field = args.field(config)?;
+	if !field.is_initialized() {
+		let limit = field.get_limit();
+		let start = Coordintes {
+			x: limit.x / 2,
+			y: limit.y / 2,
+			z: 0,
+		};
+		field.init(&start, args.get_seed())?;
+	}
+	field
+		.board_code()
+		.ok_or_else(|| Error::new(&fl!("error-race-board")))
+}
+
+// starts hosting on a thread of its own and returns the address to join it at
+pub fn host(addr: &str, players: usize, code: BoardCode) -> Result<SocketAddr, Error> {
+	let failed =
+		|e: std::io::Error| Error::new(&fl!("error-race-host", addr = addr, error = e.to_string()));
+	let listener = TcpListener::bind(addr).map_err(failed)?;
+	let mut local = listener.local_addr().map_err(failed)?;
+	if local.ip().is_unspecified() {
+		local.set_ip(match local {
+			SocketAddr::V4(_) => Ipv4Addr::LOCALHOST.into(),
+			SocketAddr::V6(_) => Ipv6Addr::LOCALHOST.into(),
+		});
+	}
+
+	thread::spawn(move || serve(listener, players.max(1), code));
+	Ok(local)
+}
+
+// the first line of a new connection has to be a join of the same version, the name is returned
+fn read_join(stream: &TcpStream) -> Option<(String, BufReader<TcpStream>)> {
+	stream.set_read_timeout(Some(JOIN_TIMEOUT)).ok()?;
+	let mut reader = BufReader::new(stream.try_clone().ok()?);
+	let mut line = String::new();
+	reader.read_line(&mut line).ok()?;
+	stream.set_read_timeout(None).ok()?;
+
+	match parse(&line)? {
+		Message::Join { version, name } if version == RACE_VERSION => Some((name, reader)),
+		_ => None,
+	}
+}
+
+// messages of a player are passed on with its number, None once it is gone
+fn forward(player: usize, reader: BufReader<TcpStream>, sender: Sender<(usize, Option<Message>)>) {
+	thread::spawn(move || {
+		for line in reader.lines().map_while(Result::ok) {
+			if let Some(message) = parse(&line) {
+				if sender.send((player, Some(message))).is_err() {
+					return;
+				}
+			}
+		}
+		_ = sender.send((player, None));
+	});
+}
+
+fn serve(listener: TcpListener, players: usize, code: BoardCode) {
+	let (sender, messages) = mpsc::channel();
+	let mut streams = Vec::new();
+	let mut standings = Vec::new();
+
+	while streams.len() < players {
+		let stream = match listener.accept() {
+			Ok((s, _)) => s,
+			Err(_) => continue,
+		};
+		let (name, reader) = match read_join(&stream) {
+			Some(j) => j,
+			None => continue,
+		};
+		forward(streams.len(), reader, sender.clone());
+		streams.push(stream);
+		standings.push(Standing::new(name));
+
+		let waiting = Message::Waiting {
+			joined: streams.len(),
+			players,
+		};
+		for stream in &mut streams {
+			send(stream, &waiting);
+		}
+	}
+	drop(sender);
+
+	let started = Instant::now();
+	let start = Message::Start {
+		code: code.to_string(),
+	};
+	for stream in &mut streams {
+		send(stream, &start);
+	}
+
+	while let Ok((player, message)) = messages.recv() {
+		let standing = &mut standings[player];
+		match message {
+			// finishing and leaving afterwards still counts as finished
+			None => standing.left = standing.time.is_none(),
+			Some(Message::Progress {
+				cleared,
+				deaths,
+				finished,
+			}) => {
+				if standing.time.is_some() {
+					continue;
+				}
+				standing.cleared = cleared;
+				standing.deaths = deaths;
+				if finished {
+					standing.time = Some(started.elapsed().as_secs_f64());
+				}
+			}
+			Some(_) => continue,
+		}
+
+		let over = standings.iter().all(|s| s.left || s.time.is_some());
+		let message = Message::Standings {
+			over,
+			players: standings.clone(),
+		};
+		for stream in &mut streams {
+			send(stream, &message);
+		}
+		if over {
+			return;
+		}
+	}
+}
+
+// what the host sent, read on a thread of its own so the game never waits for the network
+enum Event {
+	Waiting { joined: usize, players: usize },
+	Start(BoardCode),
+	Standings { players: Vec<Standing>, over: bool },
+	Closed,
+}
+
+fn parse_event(message: Message) -> Option<Event> {
+	match message {
+		Message::Waiting { joined, players } => Some(Event::Waiting { joined, players }),
+		Message::Start { code } => Some(Event::Start(code.parse().ok()?)),
+		Message::Standings { over, players } => Some(Event::Standings { players, over }),
+		_ => None,
+	}
+}
+
+// a player taking part in a race
+pub struct Race {
+	stream: TcpStream,
+	events: Receiver<Event>,
+	pub code: Option<BoardCode>,
+	pub standings: Vec<Standing>,
+	// players that joined so far and how many are waited for
+	pub joined: (usize, usize),
+	pub deaths: u32,
+	pub over: bool,
+	pub closed: bool,
+	// whether the last board reported was lost, so every death is only counted once
+	lost: bool,
+	finished: bool,
+}
+
+impl Race {
+	// hosts a race if the arguments ask for one, then joins it, or joins the race of someone else
+	pub fn from_args(args: &PlayArgs, config: &Config) -> Result<Option<Race>, Error> {
+		let name = args.name.clone().unwrap_or_else(default_name);
+		if let Some(addr) = &args.host {
+			let local = host(addr, args.players, board(&args.board, config)?)?;
+			return Race::join(&local.to_string(), &name).map(Some);
+		}
+		match &args.join {
+			None => Ok(None),
+			Some(addr) => Race::join(addr, &name).map(Some),
+		}
+	}
+
+	pub fn join(addr: &str, name: &str) -> Result<Race, Error> {
+		let failed = |e: std::io::Error| {
+			Error::new(&fl!(
+				"error-race-connect",
+				addr = addr,
+				error = e.to_string()
+			))
+		};
+		let mut stream = TcpStream::connect(addr).map_err(failed)?;
+		let reader = BufReader::new(stream.try_clone().map_err(failed)?);
+		let join = Message::Join {
+			version: RACE_VERSION,
+			name: name.to_string(),
+		};
+		if !send(&mut stream, &join) {
+			return Err(Error::new(&fl!("error-race-closed")));
+		}
+
+		let (sender, events) = mpsc::channel();
+		thread::spawn(move || {
+			for line in reader.lines().map_while(Result::ok) {
+				let event = parse(&line).and_then(parse_event);
+				if let Some(e) = event {
+					if sender.send(e).is_err() {
+						return;
+					}
+				}
+			}
+			_ = sender.send(Event::Closed);
+		});
+
+		Ok(Race {
+			stream,
+			events,
+			code: None,
+			standings: Vec::new(),
+			joined: (0, 0),
+			deaths: 0,
+			over: false,
+			closed: false,
+			lost: false,
+			finished: false,
+		})
+	}
+
+	fn apply(&mut self, event: Event) {
+		match event {
+			Event::Waiting { joined, players } => self.joined = (joined, players),
+			Event::Start(code) => self.code = Some(code),
+			Event::Standings { players, over } => {
+				self.standings = players;
+				self.over = over;
+			}
+			Event::Closed => self.closed = true,
+		}
+	}
+
+	// takes in everything the host sent so far without waiting, returns whether anything changed
+	pub fn poll(&mut self) -> bool {
+		let mut changed = false;
+		while let Ok(event) = self.events.try_recv() {
+			self.apply(event);
+			changed = true;
+		}
+		changed
+	}
+
+	// waits for the next message of the host, returns false once the connection is gone
+	pub fn wait(&mut self) -> bool {
+		match self.events.recv() {
+			Ok(event) => self.apply(event),
+			Err(_) => self.closed = true,
+		}
+		!self.closed
+	}
+
+	pub fn started(&self) -> bool {
+		self.code.is_some()
+	}
+
+	// sends how far the player got on the field, deaths are counted here
+	pub fn report(&mut self, field: &Field) {
+		if self.finished {
+			return;
+		}
+		if field.has_lost() && !self.lost {
+			self.deaths += 1;
+		}
+		self.lost = field.has_lost();
+		self.finished = !field.has_lost() && field.is_initialized() && field.victory();
+
+		let progress = Message::Progress {
+			cleared: cleared(field),
+			deaths: self.deaths,
+			finished: self.finished,
+		};
+		if !send(&mut self.stream, &progress) {
+			self.closed = true;
+		}
+	}
+
+	// the line shown while waiting for the race to start
+	pub fn waiting(&self) -> String {
+		fl!(
+			"race-waiting",
+			joined = self.joined.0,
+			players = self.joined.1
+		)
+	}
+
+	// the standings, one player per line
+	pub fn describe(&self) -> String {
+		let mut lines: Vec<String> = self.standings.iter().map(Standing::describe).collect();
+		if self.over {
+			lines.push(fl!("race-over"));
+		} else if self.closed {
+			lines.push(fl!("error-race-closed"));
+		}
+		lines.join("\n")
+	}
+}
+
+// the reading thread holds a copy of the connection, so it has to be shut down for the host to notice
+impl Drop for Race {
+	fn drop(&mut self) {
+		_ = self.stream.shutdown(Shutdown::Both);
+	}
+}
+
+fn default_name() -> String {
+	std::env::var("USER")
+		.or_else(|_| std::env::var("USERNAME"))
+		.unwrap_or_else(|_| fl!("race-default-name"))
+}
+
+#[cfg(test)]
+mod race_tests {
+	use super::*;
+
+	#[test]
+	fn test_race() -> Result<(), Error> {
+		let mut field = Field::new(9, 9, 10);
+		let start = Coordintes { x: 4, y: 4, z: 0 };
+		field.init(&start, 3)?;
+		let code = field.board_code().ok_or(Error::new("no code"))?;
+
+		let addr = host("127.0.0.1:0", 2, code.clone())?.to_string();
+		let mut alice = Race::join(&addr, "alice")?;
+		let mut bob = Race::join(&addr, "bob")?;
+		for player in [&mut alice, &mut bob] {
+			while !player.started() {
+				if !player.wait() {
+					return Err(Error::new("race did not start"));
+				}
+			}
+			if player.code.as_ref() != Some(&code) {
+				return Err(Error::new("players got different boards"));
+			}
+		}
+
+		// alice dies once, then clears and flags the board, bob gives up
+		let mut board = code.play()?;
+		let mine = board
+			.get_field()
+			.iter()
+			.position(|t| t.is_mine)
+			.ok_or(Error::new("no mine"))?;
+		let mut lost = board.clone();
+		lost.reveal(&Coordintes {
+			x: (mine % 9) as u8,
+			y: (mine / 9) as u8,
+			z: 0,
+		})?;
+		alice.report(&lost);
+		alice.report(&lost);
+		for y in 0..9 {
+			for x in 0..9 {
+				let c = Coordintes { x, y, z: 0 };
+				if board.is_mine(&c)? {
+					board.flag(&c)?;
+				} else {
+					board.reveal(&c)?;
+				}
+			}
+		}
+		alice.report(&board);
+		drop(bob);
+
+		while !alice.over {
+			if !alice.wait() {
+				return Err(Error::new("race did not end"));
+			}
+		}
+		let standings = &alice.standings;
+		if standings.len() != 2
+			|| standings[0].deaths != 1
+			|| standings[0].time.is_none()
+			|| standings[0].cleared != 1.0
+			|| !standings[1].left
+		{
+			return Err(Error::new("standings are wrong"));
+		}
+
+		Ok(())
+	}
+}
@@ -7,9 +7,20 @@ use atoi::atoi;
 use clap::Parser;
 
 use crate::{
-	args::Args,
+	args::{Args, PlayArgs},
+	config::Config,
+	daily::{DailyStats, Date},
 	error::Error,
-	field::{tile::Coordintes, Field},
+	field::{
+		moves::{self, Move, Progress},
+		puzzle,
+		tile::Coordintes,
+		versus::{self, Turn, Versus},
+		Field,
+	},
+	fl,
+	localization::describe_tile,
+	race::Race,
 };
 
 #[derive(Debug)]
@@ -18,6 +29,9 @@ enum Action {
 	Flag,
 	Unknown,
 	Cheat,
+	// moves the shown layer of three dimensional fields up or down
+	Layer(i8),
+	Quit,
 	None,
 }
 #[derive(Debug)]
@@ -27,18 +41,70 @@ struct Choice {
 }
 
 pub fn run_tui() {
-	let args = Args::parse();
+	let args = Args::parse().play().clone();
+	let config = Config::load();
 
-	let mut f = Field::new(args.width, args.height, args.mines);
+	let daily = args.board.daily();
+	if let Some(date) = daily {
+		println!("{}", fl!("daily-title", date = date.to_string()));
+	}
+
+	let mut race = match Race::from_args(&args, &config) {
+		Ok(r) => r,
+		Err(e) => {
+			e.fatal();
+			None
+		}
+	};
+
+	let field = if let Some(r) = &mut race {
+		join_race(r)
+	} else if args.puzzles.is_some() {
+		choose_puzzle(&args)
+	} else {
+		match args.board.field(&config) {
+			Ok(f) => Some(f),
+			Err(e) => {
+				e.fatal();
+				None
+			}
+		}
+	};
+	let mut f = match field {
+		Some(f) => f,
+		None => return,
+	};
+	let layers = f.get_limit().z;
+	args.export(&f);
+	// places the mines on the first reveal, boards from codes or files already have theirs
+	let seed = args.board.get_seed();
 
 	let mut choice = Choice {
-		coords: Coordintes { x: 0, y: 0 },
+		coords: Coordintes::new(),
 		action: Action::None,
 	};
+	// only one layer is shown at a time, the same one all moves are made on
+	let mut layer = 0;
+	// two players share the keyboard, taking turns
+	let mut versus = args.versus.then(Versus::new);
+	// versus games are not played alone, so they do not count for the daily stats
+	let daily = daily.filter(|_| versus.is_none());
 
 	loop {
-		println!("{}", f);
-		print!("Choose action (x,y,action:[r,f,?]): ");
+		if args.describe {
+			println!("{}", describe_field(&f, layer));
+		} else if layers > 1 {
+			println!("{}", f.summary());
+			println!("{}", fl!("tui-layers"));
+			println!("{}", f.describe_layer(layer));
+			println!("{}", f.layer_to_string(layer));
+		} else {
+			println!("{}", f);
+		}
+		if let Some(v) = &versus {
+			println!("{}", v.describe(&f));
+		}
+		print!("{} ", fl!("tui-prompt"));
 		_ = io::stdout().flush();
 		let stdin = io::stdin();
 		for line in stdin.lock().lines() {
@@ -50,89 +116,363 @@ pub fn run_tui() {
 				}
 				Ok(text) => {
 					if text.to_lowercase() == "q" {
-						println!("Quitting...");
-						process::exit(0);
+						choice.action = Action::Quit;
+						break;
 					}
 					match parse_choice(text.to_lowercase()) {
 						Err(e) => e.fatal(),
 						Ok(c) => choice = c,
 					}
+					choice.coords.z = layer;
 					break; // only read one line
 				}
 			}
 		}
 
-		match choice.action {
+		let progress = match choice.action {
 			Action::Reveal => {
 				if !f.is_initialized() {
-					let seed = args.get_seed();
 					if let Err(e) = f.init(&choice.coords, seed) {
 						e.fatal()
 					}
+					args.export(&f);
 
-					println!("Seed: {}", seed)
+					println!(
+						"{}",
+						fl!(
+							"tui-seed",
+							seed = seed.to_string(),
+							placement = f.get_placement()
+						)
+					)
 				}
-				match f.reveal(&choice.coords) {
-					Err(e) => e.fatal(),
-					Ok(t) => {
-						if t.is_mine {
-							println!("You died :(");
-							f.print_revealed();
-							process::exit(0);
+				if let Some(v) = &mut versus {
+					match v.play(&mut f, &choice.coords) {
+						Err(e) => e.fatal(),
+						Ok(Turn::Found) => {
+							println!("{}", fl!("versus-found", player = (v.player + 1)))
 						}
+						Ok(_) => (),
+					}
+					if versus::over(&f) {
+						println!("{}", v.describe(&f));
+						f.print_revealed();
+						game_over(&f, daily);
+						break;
 					}
+					continue;
 				}
-			}
-			Action::Flag => {
-				if let Err(e) = f.flag(&choice.coords) {
-					e.fatal();
+
+				let progress = play(&mut f, Move::Reveal, &choice.coords, seed);
+				if args.describe {
+					let state = match f.get_tile(&choice.coords) {
+						Ok(t) => describe_tile(t, true),
+						Err(e) => {
+							e.fatal();
+							continue;
+						}
+					};
+					let mut announcement = fl!(
+						"announce-tile",
+						row = (choice.coords.y + 1),
+						column = (choice.coords.x + 1),
+						state = state
+					);
+					if layers > 1 {
+						announcement = fl!(
+							"tile-in-layer",
+							layer = f.describe_layer(layer),
+							tile = announcement
+						);
+					}
+					println!("{}", announcement);
 				}
+				if progress == Progress::Lost {
+					println!("{}", fl!("tui-lost"));
+					f.print_revealed();
+					// races go on from the start of the same board
+					if let Some(r) = &mut race {
+						r.report(&f);
+						println!("{}", fl!("race-restart"));
+						if let Some(code) = &r.code {
+							match code.play() {
+								Ok(field) => f = field,
+								Err(e) => e.fatal(),
+							}
+						}
+						r.report(&f);
+						r.poll();
+						println!("{}", r.describe());
+						continue;
+					}
+					game_over(&f, daily);
+					process::exit(0);
+				}
+				progress
 			}
+			Action::Flag => play(&mut f, Move::Flag, &choice.coords, seed),
 			Action::Unknown => {
-				if let Err(e) = f.mark_unknown(&choice.coords) {
-					e.fatal()
+				if !config.question_marks {
+					println!("{}", fl!("tui-question-marks-disabled"));
+					continue;
 				}
+				play(&mut f, Move::Unknown, &choice.coords, seed)
 			}
 			Action::Cheat => {
-				f.print_revealed();
+				if race.is_none() && versus.is_none() {
+					f.print_revealed();
+				}
+				continue;
+			}
+			Action::Layer(step) => {
+				layer = (layer as i16 + step as i16).clamp(0, layers as i16 - 1) as u8;
+				continue;
+			}
+			Action::Quit => {
+				if config.confirm_prompts
+					&& f.is_initialized()
+					&& !confirm(&fl!("tui-confirm-quit"))
+				{
+					continue;
+				}
+				println!("{}", fl!("tui-quitting"));
+				process::exit(0);
 			}
 			Action::None => {
 				continue;
 			}
+		};
+
+		if let Some(r) = &mut race {
+			r.report(&f);
+			r.poll();
+			println!("{}", r.describe());
 		}
 
-		if f.victory() {
-			println!("You Won!");
+		// versus games end by finding mines, not by flagging them
+		if versus.is_none() && progress == Progress::Won {
+			println!("{}", fl!("tui-won"));
 			f.print_revealed();
+			if let Some(r) = &mut race {
+				// the others can still be seen playing until everyone is done
+				while !r.over && r.wait() {
+					println!("{}", r.describe());
+				}
+			}
+			game_over(&f, daily);
 			break;
 		}
 	}
 }
 
+// moves that cannot be made are bugs of the tui, so they end the game
+fn play(f: &mut Field, action: Move, coords: &Coordintes, seed: u64) -> Progress {
+	match moves::play(f, action, coords, seed) {
+		Ok(p) => p,
+		Err(e) => {
+			e.fatal();
+			Progress::Playing
+		}
+	}
+}
+
+// one line per row, neighbouring tiles in the same state are grouped so the output stays short
+// three dimensional fields are described one layer at a time
+fn describe_field(f: &Field, layer: u8) -> String {
+	let limit = f.get_limit();
+	let tiles = f.get_field();
+	let remaining = f.get_num_mines() as i32 - f.num_flags() as i32;
+	let mut out = fl!("mines-remaining", count = remaining) + "\n";
+	for legend in f.legend() {
+		out += &legend;
+		out += "\n";
+	}
+	if limit.z > 1 {
+		out += &fl!("tui-layers");
+		out += "\n";
+		out += &f.describe_layer(layer);
+		out += "\n";
+	}
+
+	let layer_start = layer as usize * limit.x as usize * limit.y as usize;
+	for y in 0..limit.y {
+		let start = layer_start + y as usize * limit.x as usize;
+		let row = &tiles[start..start + limit.x as usize];
+		let mut parts = Vec::new();
+		let mut start = 0;
+		while start < row.len() {
+			let state = describe_tile(&row[start], f.has_lost());
+			let mut end = start;
+			while end + 1 < row.len() && describe_tile(&row[end + 1], f.has_lost()) == state {
+				end += 1;
+			}
+
+			if start == end {
+				parts.push(fl!("tui-column", column = (start + 1), state = state));
+			} else {
+				parts.push(fl!(
+					"tui-columns",
+					start = (start + 1),
+					end = (end + 1),
+					state = state
+				));
+			}
+			start = end + 1;
+		}
+
+		out += &fl!("tui-row", row = (y + 1), tiles = parts.join("; "));
+		out += "\n";
+	}
+
+	out
+}
+
+// waits until every player joined, then plays the board the host sent
+fn join_race(race: &mut Race) -> Option<Field> {
+	while !race.started() {
+		if !race.wait() {
+			Error::new(&fl!("error-race-closed")).fatal();
+			return None;
+		}
+		if !race.started() {
+			println!("{}", race.waiting());
+		}
+	}
+
+	println!("{}", fl!("race-started"));
+	match race.code.as_ref()?.play() {
+		Ok(f) => Some(f),
+		Err(e) => {
+			e.fatal();
+			None
+		}
+	}
+}
+
+// lists the puzzles of the directory and asks which one to play, broken files are listed but cannot be picked
+fn choose_puzzle(args: &PlayArgs) -> Option<Field> {
+	let dir = match args.puzzle_dir() {
+		None => {
+			Error::new(&fl!("error-puzzle-dir")).fatal();
+			return None;
+		}
+		Some(d) => d,
+	};
+	let puzzles = match puzzle::load_dir(&dir) {
+		Err(e) => {
+			e.fatal();
+			return None;
+		}
+		Ok(p) => p,
+	};
+	if puzzles.is_empty() {
+		println!("{}", fl!("puzzles-empty", dir = dir.display().to_string()));
+		return None;
+	}
+
+	for (i, (path, puzzle)) in puzzles.iter().enumerate() {
+		let line = match puzzle {
+			Ok(p) if p.is_unique() => p.name.clone(),
+			Ok(p) => fl!("puzzle-needs-guess", name = p.name.clone()),
+			Err(e) => fl!(
+				"puzzle-broken",
+				name = path.display().to_string(),
+				error = e.message()
+			),
+		};
+		println!("{}. {}", i + 1, line);
+	}
+
+	loop {
+		print!("{} ", fl!("tui-puzzle-prompt"));
+		_ = io::stdout().flush();
+
+		let mut answer = String::new();
+		if let Err(e) = io::stdin().lock().read_line(&mut answer) {
+			Error::new(&e.to_string()).fatal();
+		}
+		let answer = answer.trim();
+		if answer.to_lowercase() == "q" {
+			println!("{}", fl!("tui-quitting"));
+			return None;
+		}
+
+		let chosen = answer
+			.parse::<usize>()
+			.ok()
+			.and_then(|n| puzzles.get(n.wrapping_sub(1)));
+		match chosen {
+			Some((_, Ok(p))) => {
+				println!("{}", fl!("puzzle-title", name = p.name.clone()));
+				return Some(p.field.clone());
+			}
+			Some((_, Err(e))) => e.out(),
+			None => println!("{}", fl!("error-puzzle-choice", count = puzzles.len())),
+		}
+	}
+}
+
+// results of daily challenges are only recorded on the day itself, earlier days are just for practice
+fn game_over(f: &Field, daily: Option<Date>) {
+	if let Some(code) = f.board_code() {
+		println!("{}", fl!("board-code", code = code.to_string()));
+	}
+
+	if let Some(date) = daily {
+		let today = Date::today();
+		let mut stats = DailyStats::load();
+		if date == today && stats.record(date, !f.has_lost()) {
+			if let Err(e) = stats.save() {
+				e.out();
+			}
+		}
+		println!("{}", stats.summary(today));
+	}
+}
+
+// asks a yes/no question, anything but the localized "y" counts as no
+fn confirm(question: &str) -> bool {
+	print!("{} {} ", question, fl!("tui-yes-no"));
+	_ = io::stdout().flush();
+
+	let mut answer = String::new();
+	if let Err(e) = io::stdin().lock().read_line(&mut answer) {
+		Error::new(&e.to_string()).fatal();
+	}
+
+	answer.trim().to_lowercase() == fl!("tui-yes")
+}
+
 fn parse_choice(text: String) -> Result<Choice, Error> {
-	if text == "cheat" {
+	let special = match text.as_str() {
+		"cheat" => Some(Action::Cheat),
+		"<" => Some(Action::Layer(-1)),
+		">" => Some(Action::Layer(1)),
+		_ => None,
+	};
+	if let Some(action) = special {
 		return Ok(Choice {
-			coords: Coordintes { x: 0, y: 0 },
-			action: Action::Cheat,
+			coords: Coordintes::new(),
+			action,
 		});
 	}
 
 	if text.len() != 5 {
-		return Err(Error::new("input too short"));
+		return Err(Error::new(&fl!("error-input-length")));
 	}
 
 	let chars: Vec<char> = text.chars().collect();
 
 	if chars[1] != ',' || chars[3] != ',' {
-		return Err(Error::new("not separated properly"));
+		return Err(Error::new(&fl!("error-separator")));
 	}
 
 	let xar = [chars[0] as u8];
 	let x: u8 = match atoi::<u8>(&xar) {
-		None => return Err(Error::new("NaN")),
+		None => return Err(Error::new(&fl!("error-nan"))),
 		Some(v) => {
 			if v == 0 {
-				return Err(Error::new("Outside coordinate grid"));
+				return Err(Error::new(&fl!("error-outside-coordinates")));
 			}
 			v - 1
 		}
@@ -141,10 +481,10 @@ fn parse_choice(text: String) -> Result<Choice, Error> {
 	let yar = [chars[2] as u8];
 
 	let y: u8 = match atoi::<u8>(&yar) {
-		None => return Err(Error::new("NaN")),
+		None => return Err(Error::new(&fl!("error-nan"))),
 		Some(v) => {
 			if v == 0 {
-				return Err(Error::new("Outside coordinate grid"));
+				return Err(Error::new(&fl!("error-outside-coordinates")));
 			}
 			v - 1
 		}
@@ -154,11 +494,11 @@ fn parse_choice(text: String) -> Result<Choice, Error> {
 		'?' => Action::Unknown,
 		'f' => Action::Flag,
 		'r' => Action::Reveal,
-		_ => return Err(Error::new("unknown action")),
+		_ => return Err(Error::new(&fl!("error-unknown-action"))),
 	};
 
 	Ok(Choice {
-		coords: Coordintes { x, y },
+		coords: Coordintes { x, y, z: 0 },
 		action,
 	})
 }